@@ -0,0 +1,471 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! `RatatuiRuby::Buffer::Buffer`, a grid of cells Ruby can read and write.
+//!
+//! A buffer either owns its cells or borrows a frame's. `render_to_buffer` draws a
+//! widget tree (or a block) into an offscreen terminal of the requested size and keeps
+//! only its buffer. Nothing global is replaced, so apps can pre-render panels, render
+//! on a server, or test widgets from several threads.
+//!
+//! Custom widgets whose `render` takes two arguments receive the frame's buffer
+//! itself, as Rust widgets do, so they can read what is underneath, write without
+//! building draw commands, and render built-in widgets inside themselves. Writes
+//! outside the buffer are clipped instead of raising.
+//!
+//! # Safety
+//!
+//! A borrowed buffer holds a pointer to the frame with an erased lifetime, like
+//! `RubyFrame`. It is only created by `lend`, which clears its `active` flag as soon
+//! as the Ruby code it was lent to returns, and every method checks that flag before
+//! touching the pointer.
+
+use magnus::{function, method, prelude::*, Error, RArray, RHash, Ruby, Value};
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    Frame, Terminal,
+};
+use std::cell::{RefCell, UnsafeCell};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use unicode_width::UnicodeWidthStr;
+
+use crate::color_depth::{self, ColorDepth};
+use crate::hyperlink::LinkTargets;
+use crate::terminal::terminal_error;
+
+enum Storage {
+    Owned {
+        buffer: RefCell<Buffer>,
+        links: RefCell<LinkTargets>,
+    },
+    /// A frame's buffer, lent to a custom widget for one `render` call.
+    Borrowed {
+        frame: UnsafeCell<NonNull<Frame<'static>>>,
+        active: Arc<AtomicBool>,
+    },
+}
+
+#[magnus::wrap(class = "RatatuiRuby::Buffer::Buffer")]
+pub struct RubyBuffer {
+    storage: Storage,
+}
+
+// SAFETY: Borrowed buffers are only used during the draw callback that lent them, on
+// the thread holding the GVL.
+unsafe impl Send for RubyBuffer {}
+
+impl RubyBuffer {
+    /// Wraps a copy of a buffer drawn on a screen, along with the targets of its links.
+    pub fn copy_of(buffer: &Buffer) -> Self {
+        Self {
+            storage: Storage::Owned {
+                buffer: RefCell::new(buffer.clone()),
+                links: RefCell::new(LinkTargets::copy(buffer)),
+            },
+        }
+    }
+
+    /// Checks that a borrowed buffer is still lent out.
+    fn ensure_active(active: &AtomicBool) -> Result<(), Error> {
+        if active.load(Ordering::Relaxed) {
+            Ok(())
+        } else {
+            let ruby = Ruby::get().unwrap();
+            let module = ruby.define_module("RatatuiRuby")?;
+            let error_base = module.const_get::<_, magnus::RClass>("Error")?;
+            let error_class = error_base.const_get("Safety")?;
+            Err(Error::new(
+                error_class,
+                "Buffer cannot be used outside of the render call",
+            ))
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Buffer) -> T) -> Result<T, Error> {
+        self.read_linked(|buffer, _| f(buffer))
+    }
+
+    /// Like `read`, also passing where the buffer's link targets are looked up.
+    fn read_linked<T>(&self, f: impl FnOnce(&Buffer, &LinkTargets) -> T) -> Result<T, Error> {
+        match &self.storage {
+            Storage::Owned { buffer, links } => Ok(f(&buffer.borrow(), &links.borrow())),
+            Storage::Borrowed { frame, active } => {
+                Self::ensure_active(active)?;
+                // SAFETY: The frame outlives the render call, and `active` is only set
+                // during it.
+                let buffer = unsafe { (*frame.get()).as_mut() }.buffer_mut();
+                Ok(f(buffer, &LinkTargets::Shared))
+            }
+        }
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Buffer) -> T) -> Result<T, Error> {
+        match &self.storage {
+            Storage::Owned { buffer, .. } => Ok(f(&mut buffer.borrow_mut())),
+            Storage::Borrowed { frame, active } => {
+                Self::ensure_active(active)?;
+                // SAFETY: As in `read`. Ruby code cannot run while `f` holds the buffer.
+                Ok(f(unsafe { (*frame.get()).as_mut() }.buffer_mut()))
+            }
+        }
+    }
+
+    /// Moves links about to be written into an owned buffer into its own targets.
+    fn adopt<'a>(&self, styles: impl IntoIterator<Item = &'a mut Style>) {
+        if let Storage::Owned { buffer, links } = &self.storage {
+            links.borrow_mut().adopt(styles, &buffer.borrow());
+        }
+    }
+
+    pub fn width(&self) -> Result<u16, Error> {
+        self.read(|buffer| buffer.area.width)
+    }
+
+    pub fn height(&self) -> Result<u16, Error> {
+        self.read(|buffer| buffer.area.height)
+    }
+
+    /// Returns the area the buffer covers as a `Layout::Rect`.
+    pub fn area(&self) -> Result<Value, Error> {
+        crate::rendering::rect_value(self.read(|buffer| buffer.area)?)
+    }
+
+    pub fn content(&self) -> Result<String, Error> {
+        self.read(crate::terminal::buffer_content)
+    }
+
+    pub fn cell_at(&self, x: u16, y: u16) -> Result<RHash, Error> {
+        self.read_linked(|buffer, links| crate::terminal::cell_at(buffer, x, y, links))?
+    }
+
+    pub fn to_ansi(&self) -> Result<String, Error> {
+        self.read_linked(crate::export::to_ansi)
+    }
+
+    pub fn to_html(&self) -> Result<String, Error> {
+        self.read_linked(crate::export::to_html)
+    }
+
+    pub fn to_svg(&self) -> Result<String, Error> {
+        self.read_linked(crate::export::to_svg)
+    }
+
+    /// Returns every styled region as a Hash, row by row.
+    pub fn regions(&self) -> Result<RArray, Error> {
+        let ruby = Ruby::get().unwrap();
+        let regions = self.read_linked(|buffer, links| {
+            regions(buffer)
+                .into_iter()
+                .map(|region| {
+                    let url = buffer
+                        .cell((region.x, region.y))
+                        .and_then(|cell| links.url(cell));
+                    (region, url)
+                })
+                .collect::<Vec<_>>()
+        })?;
+        let array = ruby.ary_new();
+        for (region, url) in regions {
+            let hash = ruby.hash_new();
+            hash.aset(ruby.to_symbol("x"), region.x)?;
+            hash.aset(ruby.to_symbol("y"), region.y)?;
+            hash.aset(ruby.to_symbol("width"), region.width)?;
+            hash.aset(ruby.to_symbol("text"), region.text)?;
+            hash.aset(
+                ruby.to_symbol("fg"),
+                crate::terminal::color_to_value(region.fg),
+            )?;
+            hash.aset(
+                ruby.to_symbol("bg"),
+                crate::terminal::color_to_value(region.bg),
+            )?;
+            hash.aset(
+                ruby.to_symbol("modifiers"),
+                crate::terminal::modifiers_to_value(region.modifier),
+            )?;
+            hash.aset(ruby.to_symbol("hyperlink"), url)?;
+            array.push(hash)?;
+        }
+        Ok(array)
+    }
+
+    /// Writes `string` from `(x, y)`, stopping at the buffer's right edge.
+    ///
+    /// Returns the position after the last character written.
+    pub fn set_string(
+        &self,
+        x: i64,
+        y: i64,
+        string: String,
+        style: Value,
+    ) -> Result<(i64, i64), Error> {
+        let style = crate::style::parse_style(style)?;
+        self.write(|buffer| match position_in(buffer.area, x, y) {
+            Some(position) => {
+                let width = usize::from(buffer.area.right() - position.x);
+                let end = buffer.set_stringn(position.x, position.y, string, width, style);
+                (i64::from(end.0), i64::from(end.1))
+            }
+            None => (x, y),
+        })
+    }
+
+    /// Writes a `Text::Line` (or String) from `(x, y)`, at most `max_width` cells wide.
+    ///
+    /// Returns the position after the last character written.
+    pub fn set_line(
+        &self,
+        x: i64,
+        y: i64,
+        line: Value,
+        max_width: Option<u16>,
+    ) -> Result<(i64, i64), Error> {
+        let mut line = match String::try_convert(line) {
+            Ok(text) => Line::raw(text),
+            Err(_) => crate::text::parse_line(line)?,
+        };
+        self.adopt(
+            std::iter::once(&mut line.style)
+                .chain(line.spans.iter_mut().map(|span| &mut span.style)),
+        );
+        self.write(|buffer| match position_in(buffer.area, x, y) {
+            Some(position) => {
+                let room = buffer.area.right() - position.x;
+                let width = max_width.map_or(room, |max| max.min(room));
+                let end = buffer.set_line(position.x, position.y, &line, width);
+                (i64::from(end.0), i64::from(end.1))
+            }
+            None => (x, y),
+        })
+    }
+
+    /// Patches the style of every cell in `area` that is inside the buffer.
+    pub fn set_style(&self, area: Value, style: Value) -> Result<(), Error> {
+        let area = crate::rendering::parse_rect(area)?;
+        let style = crate::style::parse_style(style)?;
+        self.write(|buffer| buffer.set_style(area.intersection(buffer.area), style))
+    }
+
+    /// Replaces the cell at `(x, y)` with a `Buffer::Cell`, if it is inside the buffer.
+    pub fn set_cell(&self, x: i64, y: i64, cell: Value) -> Result<(), Error> {
+        let (symbol, mut style) = crate::rendering::parse_cell(cell)?;
+        self.adopt([&mut style]);
+        self.write(|buffer| {
+            if let Some(position) = position_in(buffer.area, x, y) {
+                buffer[position].set_symbol(&symbol).set_style(style);
+            }
+        })
+    }
+
+    /// Renders a widget into `area`, clipped to the buffer, as `Frame#render_widget` does.
+    ///
+    /// Only the buffer lent to a custom widget can render widgets.
+    pub fn render_widget(&self, widget: Value, area: Value) -> Result<(), Error> {
+        let ruby = Ruby::get().unwrap();
+        let Storage::Borrowed { frame, active } = &self.storage else {
+            return Err(Error::new(
+                ruby.exception_not_imp_error(),
+                "Only the buffer given to a custom widget's render can render widgets",
+            ));
+        };
+        let area = crate::rendering::parse_rect(area)?;
+        Self::ensure_active(active)?;
+        // Widgets rendered here may run Ruby code that kept this buffer. It stops working
+        // until they return, so nothing else uses the frame while `render_node` holds it.
+        // Custom widgets among them get buffers of their own.
+        active.store(false, Ordering::Relaxed);
+        // SAFETY: As in `read`, and this buffer is inactive while the reference lives.
+        let frame = unsafe { (*frame.get()).as_mut() };
+        let area = area.intersection(frame.buffer_mut().area);
+        let result = crate::rendering::render_node(frame, area, widget);
+        active.store(true, Ordering::Relaxed);
+        result
+    }
+}
+
+/// Returns `(x, y)` as a position if it is inside `area`.
+fn position_in(area: Rect, x: i64, y: i64) -> Option<Position> {
+    let position = Position::new(u16::try_from(x).ok()?, u16::try_from(y).ok()?);
+    area.contains(position).then_some(position)
+}
+
+/// Lends the frame's buffer to Ruby code, such as a custom widget's `render(area, buffer)`.
+///
+/// The buffer stops working when `f` returns, even if Ruby kept it.
+pub fn lend<T>(
+    frame: &mut Frame<'_>,
+    f: impl FnOnce(RubyBuffer) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let active = Arc::new(AtomicBool::new(true));
+    let ptr = NonNull::from(frame);
+    // SAFETY: Lifetime erasure, as in `RubyFrame::new`. The pointer is only used while
+    // `active` is set, which ends before this function returns.
+    let ptr: NonNull<Frame<'static>> = unsafe { std::mem::transmute(ptr) };
+    let lent = RubyBuffer {
+        storage: Storage::Borrowed {
+            frame: UnsafeCell::new(ptr),
+            active: Arc::clone(&active),
+        },
+    };
+    let result = f(lent);
+    active.store(false, Ordering::Relaxed);
+    result
+}
+
+/// A run of cells in one row that look the same.
+#[derive(Debug, PartialEq)]
+pub struct Region {
+    pub x: u16,
+    pub y: u16,
+    /// Cells covered, including those hidden behind wide characters.
+    pub width: u16,
+    /// The symbols as the terminal shows them.
+    pub text: String,
+    pub fg: Color,
+    pub bg: Color,
+    /// Includes the hyperlink ID, so differently linked runs stay apart.
+    pub modifier: Modifier,
+}
+
+/// Splits every row into runs of cells with the same colors, modifiers, and link.
+pub fn regions(buffer: &Buffer) -> Vec<Region> {
+    let area = buffer.area;
+    let mut regions = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut current: Option<Region> = None;
+        let mut hidden = 0;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if hidden > 0 {
+                hidden -= 1;
+                if let Some(region) = current.as_mut() {
+                    region.width += 1;
+                }
+                continue;
+            }
+            hidden = cell.symbol().width().saturating_sub(1);
+            match current.as_mut() {
+                Some(region)
+                    if region.fg == cell.fg
+                        && region.bg == cell.bg
+                        && region.modifier == cell.modifier =>
+                {
+                    region.width += 1;
+                    region.text.push_str(cell.symbol());
+                }
+                _ => {
+                    regions.extend(current.take());
+                    current = Some(Region {
+                        x,
+                        y,
+                        width: 1,
+                        text: cell.symbol().to_string(),
+                        fg: cell.fg,
+                        bg: cell.bg,
+                        modifier: cell.modifier,
+                    });
+                }
+            }
+        }
+        regions.extend(current);
+    }
+    regions
+}
+
+/// Renders `tree`, or the block, into a new buffer of the given size.
+pub fn render_to_buffer(
+    width: u16,
+    height: u16,
+    color_depth: Value,
+    tree: Option<Value>,
+) -> Result<RubyBuffer, Error> {
+    let ruby = Ruby::get().unwrap();
+    if tree.is_none() && !ruby.block_given() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "Must provide either a widget or a block to render_to_buffer",
+        ));
+    }
+    let depth = color_depth::parse(color_depth)?.unwrap_or(ColorDepth::TrueColor);
+    let mut offscreen = Terminal::new(TestBackend::new(width, height))
+        .map_err(|e| terminal_error(e.to_string()))?;
+    // A screen of its own keeps the frame's links until they are copied into the buffer.
+    let screen = crate::hyperlink::new_screen();
+    let links = crate::hyperlink::FrameLinks::start(screen);
+    let mut render_error: Option<Error> = None;
+    // Frames may ask for a cursor, which a buffer does not have, so the requests are dropped.
+    let drawn = offscreen
+        .draw(|f| {
+            if let Err(e) =
+                crate::teardown::catch_panic(|| crate::render_frame(&ruby, f, tree, depth))
+            {
+                render_error = Some(e);
+            }
+        })
+        .map(|completed| {
+            links.finish(completed.buffer);
+            RubyBuffer::copy_of(completed.buffer)
+        });
+    crate::hyperlink::forget_screen(screen);
+    let buffer = drawn.map_err(|e| terminal_error(e.to_string()))?;
+    match render_error {
+        Some(e) => Err(e),
+        None => Ok(buffer),
+    }
+}
+
+/// Registers the `Buffer::Buffer` class with Ruby.
+pub fn register(ruby: &Ruby, module: magnus::RModule) -> Result<(), Error> {
+    let buffer_module = module.const_get::<_, magnus::RModule>("Buffer")?;
+    let class = buffer_module.define_class("Buffer", ruby.class_object())?;
+    class.define_method("width", method!(RubyBuffer::width, 0))?;
+    class.define_method("height", method!(RubyBuffer::height, 0))?;
+    class.define_method("_content", method!(RubyBuffer::content, 0))?;
+    class.define_method("_cell_at", method!(RubyBuffer::cell_at, 2))?;
+    class.define_method("_regions", method!(RubyBuffer::regions, 0))?;
+    class.define_method("to_ansi", method!(RubyBuffer::to_ansi, 0))?;
+    class.define_method("to_html", method!(RubyBuffer::to_html, 0))?;
+    class.define_method("to_svg", method!(RubyBuffer::to_svg, 0))?;
+    class.define_method("area", method!(RubyBuffer::area, 0))?;
+    class.define_method("_set_string", method!(RubyBuffer::set_string, 4))?;
+    class.define_method("_set_line", method!(RubyBuffer::set_line, 4))?;
+    class.define_method("set_style", method!(RubyBuffer::set_style, 2))?;
+    class.define_method("_set_cell", method!(RubyBuffer::set_cell, 3))?;
+    class.define_method("render_widget", method!(RubyBuffer::render_widget, 2))?;
+    module.define_module_function("_render_to_buffer", function!(render_to_buffer, 4))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_regions_split_on_style() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(0, 0, "ab", Style::default().red());
+        buffer.set_string(2, 0, "cd", Style::default());
+        let regions = regions(&buffer);
+        let texts: Vec<_> = regions.iter().map(|r| (r.x, r.text.as_str())).collect();
+        assert_eq!(texts, [(0, "ab"), (2, "cd  ")]);
+        assert_eq!(regions[0].fg, Color::Red);
+    }
+
+    #[test]
+    fn test_regions_skip_cells_hidden_by_wide_characters() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+        buffer.set_string(0, 0, "中a", Style::default());
+        let regions = regions(&buffer);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].text, "中a");
+        assert_eq!(regions[0].width, 3);
+        assert_eq!(regions[1].y, 1);
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! How many colors the terminal can show, and mapping frames down to that.
+//!
+//! `#rrggbb` colors become 24-bit escape sequences, which 256-color and 16-color
+//! terminals render as garbage. `init_terminal` detects the depth from the environment
+//! unless the app names one, and `draw` maps every color in the rendered frame to the
+//! nearest one the terminal has. The test backend goes through the same step, so tests
+//! can simulate each depth.
+
+use magnus::{prelude::*, Error, Value};
+use ratatui::{buffer::Buffer, style::Color};
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// No colors at all, as asked for by `NO_COLOR`.
+    NoColor,
+    /// The 16 named ANSI colors.
+    Ansi16,
+    /// The xterm 256-color palette.
+    Ansi256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+impl ColorDepth {
+    fn name(self) -> &'static str {
+        match self {
+            ColorDepth::NoColor => "none",
+            ColorDepth::Ansi16 => "ansi16",
+            ColorDepth::Ansi256 => "ansi256",
+            ColorDepth::TrueColor => "truecolor",
+        }
+    }
+}
+
+static DEPTH: Mutex<ColorDepth> = Mutex::new(ColorDepth::TrueColor);
+
+/// Parses the Ruby `color_depth:` option. `nil` and `:auto` mean "detect".
+pub fn parse(value: Value) -> Result<Option<ColorDepth>, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if value.is_nil() {
+        return Ok(None);
+    }
+    let depth: magnus::Symbol = magnus::Symbol::try_convert(value)?;
+    match depth.to_string().as_str() {
+        "auto" => Ok(None),
+        "none" => Ok(Some(ColorDepth::NoColor)),
+        "ansi16" => Ok(Some(ColorDepth::Ansi16)),
+        "ansi256" => Ok(Some(ColorDepth::Ansi256)),
+        "truecolor" => Ok(Some(ColorDepth::TrueColor)),
+        other => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("Unknown color depth: {other}"),
+        )),
+    }
+}
+
+/// Detects the depth from `NO_COLOR`, `COLORTERM`, and `TERM`.
+pub fn detect() -> ColorDepth {
+    detect_from(|name| std::env::var(name).ok())
+}
+
+fn detect_from(var: impl Fn(&str) -> Option<String>) -> ColorDepth {
+    if var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return ColorDepth::NoColor;
+    }
+    let colorterm = var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorDepth::TrueColor;
+    }
+    // Windows Terminal supports 24-bit color but sets neither variable.
+    if var("WT_SESSION").is_some() {
+        return ColorDepth::TrueColor;
+    }
+    let term = var("TERM").unwrap_or_default();
+    if term == "dumb" {
+        ColorDepth::NoColor
+    } else if term.ends_with("-direct")
+        || [
+            "xterm-kitty",
+            "xterm-ghostty",
+            "alacritty",
+            "foot",
+            "wezterm",
+        ]
+        .contains(&term.as_str())
+    {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+pub fn set(depth: ColorDepth) {
+    *DEPTH.lock().unwrap() = depth;
+}
+
+pub fn get() -> ColorDepth {
+    *DEPTH.lock().unwrap()
+}
+
+/// Stops downsampling once the terminal is restored.
+pub fn reset() {
+    *DEPTH.lock().unwrap_or_else(PoisonError::into_inner) = ColorDepth::TrueColor;
+}
+
+/// Returns the depth `draw` renders for, as a Symbol.
+pub fn color_depth() -> magnus::Symbol {
+    let ruby = magnus::Ruby::get().unwrap();
+    ruby.to_symbol(get().name())
+}
+
+/// Maps every color in a rendered frame to `depth`.
+pub fn downsample(buffer: &mut Buffer, depth: ColorDepth) {
+    if depth == ColorDepth::TrueColor {
+        return;
+    }
+    for cell in &mut buffer.content {
+        cell.fg = convert(cell.fg, depth);
+        cell.bg = convert(cell.bg, depth);
+        cell.underline_color = convert(cell.underline_color, depth);
+    }
+}
+
+/// The named colors with xterm's default palette, in index order.
+const ANSI16: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// Channel values of the 6×6×6 color cube at indexes 16–231.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn convert(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::TrueColor, color) | (_, color @ Color::Reset) => color,
+        (ColorDepth::NoColor, _) => Color::Reset,
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_ansi256([r, g, b])),
+        (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16([r, g, b]),
+        (ColorDepth::Ansi16, Color::Indexed(i)) => match ANSI16.get(usize::from(i)) {
+            Some((named, _)) => *named,
+            None => nearest_ansi16(indexed_rgb(i)),
+        },
+        (_, color) => color,
+    }
+}
+
+/// Returns the index of a named color in the 16-color palette.
+pub fn ansi16_index(color: Color) -> Option<u8> {
+    let index = ANSI16.iter().position(|(named, _)| *named == color)?;
+    u8::try_from(index).ok()
+}
+
+/// Returns the RGB value a terminal with xterm's palette shows, or `None` for `Reset`.
+pub fn rgb(color: Color) -> Option<[u8; 3]> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some([r, g, b]),
+        Color::Indexed(i) => Some(indexed_rgb(i)),
+        named => ANSI16
+            .iter()
+            .find(|(candidate, _)| *candidate == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&x, y)| u32::from(x.abs_diff(y)).pow(2))
+        .sum()
+}
+
+fn nearest_ansi16(rgb: [u8; 3]) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map_or(Color::Reset, |(named, _)| *named)
+}
+
+/// Picks the closer of the nearest cube color and the nearest gray.
+fn nearest_ansi256(rgb: [u8; 3]) -> u8 {
+    let level = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v - 35) / 40,
+    };
+    let [r, g, b] = rgb.map(level);
+    let cube_index = 16 + 36 * r + 6 * g + b;
+
+    let average = rgb.iter().map(|&v| u16::from(v)).sum::<u16>() / 3;
+    let gray_step = u8::try_from((average.saturating_sub(3) / 10).min(23)).unwrap_or(23);
+    let gray_index = 232 + gray_step;
+
+    if distance(rgb, indexed_rgb(gray_index)) < distance(rgb, indexed_rgb(cube_index)) {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+fn indexed_rgb(index: u8) -> [u8; 3] {
+    match index {
+        0..=15 => ANSI16[usize::from(index)].1,
+        16..=231 => {
+            let i = index - 16;
+            [i / 36, (i / 6) % 6, i % 6].map(|level| CUBE_LEVELS[usize::from(level)])
+        }
+        _ => [8 + 10 * (index - 232); 3],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value).to_string())
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let detect = |vars: &[(&str, &str)]| detect_from(env(vars));
+        assert_eq!(
+            detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm-256color")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "tmux-256color")]), ColorDepth::Ansi256);
+        assert_eq!(detect(&[("TERM", "linux")]), ColorDepth::Ansi16);
+        assert_eq!(detect(&[("TERM", "dumb")]), ColorDepth::NoColor);
+        assert_eq!(
+            detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+            ColorDepth::NoColor
+        );
+        assert_eq!(
+            detect(&[("NO_COLOR", ""), ("TERM", "xterm-256color")]),
+            ColorDepth::Ansi256
+        );
+    }
+
+    #[test]
+    fn test_nearest_ansi256() {
+        assert_eq!(nearest_ansi256([255, 0, 0]), 196);
+        assert_eq!(nearest_ansi256([0, 0, 0]), 16);
+        assert_eq!(nearest_ansi256([128, 128, 128]), 244);
+        assert_eq!(nearest_ansi256([95, 135, 175]), 67);
+    }
+
+    #[test]
+    fn test_nearest_ansi16() {
+        assert_eq!(
+            convert(Color::Rgb(250, 10, 10), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            convert(Color::Rgb(0, 0, 100), ColorDepth::Ansi16),
+            Color::Black
+        );
+        assert_eq!(convert(Color::Indexed(4), ColorDepth::Ansi16), Color::Blue);
+        assert_eq!(
+            convert(Color::Indexed(196), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+    }
+
+    #[test]
+    fn test_no_color_resets() {
+        assert_eq!(convert(Color::Red, ColorDepth::NoColor), Color::Reset);
+        assert_eq!(
+            convert(Color::Rgb(1, 2, 3), ColorDepth::NoColor),
+            Color::Reset
+        );
+    }
+}
@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Cursor shape and visibility.
+//!
+//! Ratatui shows the cursor when a frame sets its position and hides it otherwise, and
+//! knows nothing about its shape. Frames record what they want here while rendering,
+//! and `draw` applies it once the frame is flushed.
+//!
+//! Each frame collects its own requests. A frame rendered while another is being
+//! built, such as a `Terminal#draw` inside a `RatatuiRuby.draw` block, leaves the outer
+//! frame's requests alone.
+//!
+//! The shape persists across frames until changed, like the terminal's own state.
+//! Visibility is decided anew by every frame.
+
+use magnus::{prelude::*, Error, Value};
+use ratatui::{
+    backend::Backend, crossterm::cursor::SetCursorStyle, layout::Position, Frame, Terminal,
+};
+use std::cell::Cell;
+use std::io;
+use std::sync::{Mutex, PoisonError};
+
+use crate::terminal::TerminalWrapper;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorShape {
+    /// Whatever the user configured in their terminal.
+    Default,
+    Block,
+    Underline,
+    Bar,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CursorStyle {
+    pub shape: CursorShape,
+    pub blinking: bool,
+}
+
+impl CursorStyle {
+    pub const DEFAULT: CursorStyle = CursorStyle {
+        shape: CursorShape::Default,
+        blinking: false,
+    };
+
+    fn command(self) -> SetCursorStyle {
+        match (self.shape, self.blinking) {
+            (CursorShape::Default, _) => SetCursorStyle::DefaultUserShape,
+            (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+            (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+        }
+    }
+}
+
+/// What a frame asked for.
+#[derive(Clone, Copy, Default)]
+pub struct Request {
+    style: Option<CursorStyle>,
+    positioned: bool,
+    hidden: bool,
+}
+
+thread_local! {
+    static REQUEST: Cell<Request> = Cell::new(Request::default());
+}
+
+struct State {
+    /// The style apps asked for.
+    style: CursorStyle,
+    /// The style last sent to the terminal.
+    written: CursorStyle,
+    visible: bool,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    style: CursorStyle::DEFAULT,
+    written: CursorStyle::DEFAULT,
+    visible: false,
+});
+
+/// Parses a Ruby shape Symbol: `:default`, `:block`, `:underline`, or `:bar`.
+pub fn parse_shape(value: Value) -> Result<CursorShape, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let shape: magnus::Symbol = magnus::Symbol::try_convert(value)?;
+    match shape.to_string().as_str() {
+        "default" => Ok(CursorShape::Default),
+        "block" => Ok(CursorShape::Block),
+        "underline" => Ok(CursorShape::Underline),
+        "bar" => Ok(CursorShape::Bar),
+        other => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("Unknown cursor shape: {other}"),
+        )),
+    }
+}
+
+/// Places the cursor and shows it, unless the frame also asks to hide it.
+pub fn set_position(frame: &mut Frame, position: impl Into<Position>) {
+    frame.set_cursor_position(position);
+    update_request(|request| request.positioned = true);
+}
+
+pub fn request_style(style: CursorStyle) {
+    update_request(|request| request.style = Some(style));
+}
+
+/// Hides the cursor for this frame, even if a widget placed it.
+pub fn request_hidden() {
+    update_request(|request| request.hidden = true);
+}
+
+fn update_request(f: impl FnOnce(&mut Request)) {
+    REQUEST.with(|cell| {
+        let mut request = cell.get();
+        f(&mut request);
+        cell.set(request);
+    });
+}
+
+/// Collects the requests of one frame while it renders.
+pub struct FrameRequest {
+    /// The requests of the frame this one is nested in.
+    outer: Request,
+}
+
+impl FrameRequest {
+    pub fn start() -> Self {
+        Self {
+            outer: REQUEST.with(Cell::take),
+        }
+    }
+
+    /// Returns this frame's requests, and puts the outer frame's back.
+    pub fn finish(self) -> Request {
+        REQUEST.with(|cell| cell.replace(self.outer))
+    }
+}
+
+impl Drop for FrameRequest {
+    fn drop(&mut self) {
+        REQUEST.with(|cell| cell.set(self.outer));
+    }
+}
+
+/// Applies a finished frame's requests to the terminal.
+pub fn apply(wrapper: &mut TerminalWrapper, request: Request) -> io::Result<()> {
+    let mut state = STATE.lock().unwrap();
+    if let Some(style) = request.style {
+        state.style = style;
+    }
+    state.visible = request.positioned && !request.hidden;
+    // Ratatui already hid the cursor if nothing placed it.
+    let hide = request.positioned && request.hidden;
+    match wrapper {
+        TerminalWrapper::Crossterm(terminal) => {
+            if hide {
+                terminal.hide_cursor()?;
+            }
+            if state.written != state.style {
+                ratatui::crossterm::execute!(terminal.backend_mut(), state.style.command())?;
+            }
+        }
+        TerminalWrapper::Test(terminal) => {
+            if hide {
+                let _ = terminal.hide_cursor();
+            }
+        }
+    }
+    state.written = state.style;
+    crate::teardown::set_cursor_styled(state.written != CursorStyle::DEFAULT);
+    Ok(())
+}
+
+/// Applies a finished frame's visibility to a `RatatuiRuby::Terminal` object.
+///
+/// Those terminals do not keep a cursor style, so a requested style is dropped.
+pub fn apply_offscreen<B: Backend>(terminal: &mut Terminal<B>, request: Request) {
+    if request.positioned && request.hidden {
+        let _ = terminal.hide_cursor();
+    }
+}
+
+/// Puts the user's own cursor shape back before leaving TUI mode.
+///
+/// The requested style is kept, so the next frame after a resume restores it.
+pub fn leave(backend: &mut impl io::Write) {
+    let mut state = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    if state.written != CursorStyle::DEFAULT {
+        let _ = ratatui::crossterm::execute!(backend, SetCursorStyle::DefaultUserShape);
+        state.written = CursorStyle::DEFAULT;
+    }
+    crate::teardown::set_cursor_styled(false);
+}
+
+/// Forgets the requested style when the terminal is restored or replaced.
+pub fn reset() {
+    let mut state = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    state.style = CursorStyle::DEFAULT;
+    state.written = CursorStyle::DEFAULT;
+    state.visible = false;
+}
+
+/// Returns the cursor state as `{ shape:, blinking:, visible: }`.
+pub fn get_cursor_style() -> Result<magnus::RHash, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let state = STATE.lock().unwrap();
+    let shape = match state.style.shape {
+        CursorShape::Default => "default",
+        CursorShape::Block => "block",
+        CursorShape::Underline => "underline",
+        CursorShape::Bar => "bar",
+    };
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("shape"), ruby.to_symbol(shape))?;
+    hash.aset(ruby.to_symbol("blinking"), state.style.blinking)?;
+    hash.aset(ruby.to_symbol("visible"), state.visible)?;
+    Ok(hash)
+}
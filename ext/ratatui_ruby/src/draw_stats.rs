@@ -0,0 +1,277 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Statistics about each frame `draw` sends to the terminal.
+//!
+//! Every terminal counts on its own backend, so drawing on one never shows up in the
+//! stats of another. `CountingBackend` counts the cells it is handed, which are exactly
+//! what the diff produced. The crossterm backend also writes through `CountingWriter`,
+//! which counts bytes. The test backend writes none.
+//!
+//! Native widgets render through `render_widget` and friends, which add up the time
+//! they take. Everything else that builds a frame counts as converting Ruby nodes.
+
+use magnus::{Error, RHash};
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Rect, Size},
+    widgets::{StatefulWidget, Widget},
+    Frame,
+};
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DrawStats {
+    /// Frames drawn by this terminal, including this one.
+    pub frame_count: usize,
+    /// The whole `draw`, from the start of rendering to the flushed output.
+    pub duration: Duration,
+    /// Building the frame outside native widgets: running Ruby code, including custom
+    /// widgets, and converting widget trees into Ratatui widgets.
+    pub convert_duration: Duration,
+    /// Native widgets rendering into the buffer.
+    pub render_duration: Duration,
+    /// The rest of `duration`: diffing the buffer, writing the changes, and placing the cursor.
+    pub output_duration: Duration,
+    pub cells_changed: usize,
+    pub bytes_written: u64,
+}
+
+static LAST: Mutex<Option<DrawStats>> = Mutex::new(None);
+
+thread_local! {
+    /// Time native widgets spent rendering in the frame being built on this thread.
+    static RENDER_TIME: std::cell::Cell<Duration> = const { std::cell::Cell::new(Duration::ZERO) };
+}
+
+/// Runs native rendering, adding its time to the frame's `render_duration`.
+pub fn timed<T>(render: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = render();
+    let elapsed = started.elapsed();
+    RENDER_TIME.with(|time| time.set(time.get() + elapsed));
+    result
+}
+
+/// Renders a native widget, as `Frame::render_widget` does, and times it.
+pub fn render_widget<W: Widget>(frame: &mut Frame, widget: W, area: Rect) {
+    timed(|| frame.render_widget(widget, area));
+}
+
+/// Renders a native stateful widget, as `Frame::render_stateful_widget` does, and times it.
+pub fn render_stateful_widget<W: StatefulWidget>(
+    frame: &mut Frame,
+    widget: W,
+    area: Rect,
+    state: &mut W::State,
+) {
+    timed(|| frame.render_stateful_widget(widget, area, state));
+}
+
+/// Times native rendering for one frame.
+///
+/// A frame drawn while another is being built, such as a `render_to_buffer` inside a
+/// `draw` block, times its own widgets. The outer frame's time is put back when it ends.
+pub struct RenderTimer {
+    outer: Duration,
+}
+
+impl RenderTimer {
+    pub fn start() -> Self {
+        Self {
+            outer: RENDER_TIME.with(|time| time.replace(Duration::ZERO)),
+        }
+    }
+
+    /// Returns the time this frame's native widgets took, and puts the outer frame's back.
+    pub fn finish(self) -> Duration {
+        RENDER_TIME.with(|time| time.replace(self.outer))
+    }
+}
+
+impl Drop for RenderTimer {
+    fn drop(&mut self) {
+        RENDER_TIME.with(|time| time.set(self.outer));
+    }
+}
+
+/// Bytes a `CountingWriter` wrote, shared with the `CountingBackend` that reports them.
+#[derive(Clone, Default)]
+pub struct ByteCount(Arc<AtomicU64>);
+
+/// Counts bytes on their way to the terminal.
+pub struct CountingWriter<W> {
+    inner: W,
+    bytes: ByteCount,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub fn new(inner: W, bytes: ByteCount) -> Self {
+        Self { inner, bytes }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes.0.fetch_add(written as u64, Ordering::Relaxed);
+        crate::recording::capture(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        crate::recording::flush();
+        self.inner.flush()
+    }
+}
+
+/// Counts the cells a backend is handed, which are exactly what Ratatui's diff produced,
+/// and the bytes its writer wrote.
+pub struct CountingBackend<B> {
+    inner: B,
+    cells: usize,
+    bytes: ByteCount,
+}
+
+impl<B: Backend> CountingBackend<B> {
+    /// Wraps a backend that writes no bytes.
+    pub fn new(inner: B) -> Self {
+        Self::with_bytes(inner, ByteCount::default())
+    }
+
+    /// Wraps a backend writing through a `CountingWriter` that shares `bytes`.
+    pub fn with_bytes(inner: B, bytes: ByteCount) -> Self {
+        Self {
+            inner,
+            cells: 0,
+            bytes,
+        }
+    }
+
+    /// Returns the bytes written and cells drawn since the last call, and starts over.
+    pub fn take_counts(&mut self) -> (u64, usize) {
+        (
+            self.bytes.0.swap(0, Ordering::Relaxed),
+            std::mem::take(&mut self.cells),
+        )
+    }
+}
+
+impl<B> Deref for CountingBackend<B> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B> DerefMut for CountingBackend<B> {
+    fn deref_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+}
+
+impl<B: Backend> Backend for CountingBackend<B> {
+    type Error = B::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), B::Error>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells = &mut self.cells;
+        self.inner.draw(content.inspect(|_| *cells += 1))
+    }
+
+    fn append_lines(&mut self, n: u16) -> Result<(), B::Error> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), B::Error> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> Result<(), B::Error> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> Result<Position, B::Error> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> Result<(), B::Error> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> Result<(), B::Error> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), B::Error> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> Result<Size, B::Error> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> Result<WindowSize, B::Error> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> Result<(), B::Error> {
+        self.inner.flush()
+    }
+}
+
+impl<B: Write> Write for CountingBackend<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Write::flush(&mut self.inner)
+    }
+}
+
+pub fn record(stats: DrawStats) {
+    *LAST.lock().unwrap() = Some(stats);
+}
+
+/// Forgets the last frame when the terminal is restored or replaced.
+pub fn reset() {
+    *LAST.lock().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Converts stats to the Hash `RatatuiRuby::DrawStats` is built from, with durations in seconds.
+pub fn to_hash(stats: &DrawStats) -> Result<RHash, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("frame_count"), stats.frame_count)?;
+    hash.aset(ruby.to_symbol("duration"), stats.duration.as_secs_f64())?;
+    hash.aset(
+        ruby.to_symbol("convert_duration"),
+        stats.convert_duration.as_secs_f64(),
+    )?;
+    hash.aset(
+        ruby.to_symbol("render_duration"),
+        stats.render_duration.as_secs_f64(),
+    )?;
+    hash.aset(
+        ruby.to_symbol("output_duration"),
+        stats.output_duration.as_secs_f64(),
+    )?;
+    hash.aset(ruby.to_symbol("cells_changed"), stats.cells_changed)?;
+    hash.aset(ruby.to_symbol("bytes_written"), stats.bytes_written)?;
+    Ok(hash)
+}
+
+/// Returns the stats of the last `draw` as a Hash, or `nil` before the first one.
+pub fn last_draw_stats() -> Result<Option<RHash>, Error> {
+    let last = *LAST.lock().unwrap();
+    last.as_ref().map(to_hash).transpose()
+}
@@ -0,0 +1,184 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A readable file descriptor that signals pending events.
+//!
+//! Event loops built on `IO.select` or a fiber scheduler need something to wait on.
+//! This module keeps exactly one byte in a pipe whenever `poll_event` would return an
+//! event right away: an injected event is queued, or the terminal has input.
+//!
+//! A watcher thread waits for terminal input with `poll(2)` but never reads it, so
+//! crossterm still parses every byte. After each `poll_event`, `refresh` recomputes
+//! readiness and re-arms the watcher.
+
+pub use imp::{event_io_fd, refresh, set_tty_active};
+
+#[cfg(unix)]
+mod imp {
+    use magnus::Error;
+    use std::fs::File;
+    use std::io::{IsTerminal, PipeReader, PipeWriter, Read, Write};
+    use std::os::fd::{AsRawFd, RawFd};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Condvar, Mutex, OnceLock};
+    use std::time::Duration;
+
+    struct Signal {
+        reader: PipeReader,
+        writer: PipeWriter,
+        /// Whether the pipe currently holds its byte.
+        raised: bool,
+    }
+
+    static SIGNAL: Mutex<Option<Signal>> = Mutex::new(None);
+
+    /// Whether the watcher thread should wait for terminal input.
+    static ARMED: Mutex<bool> = Mutex::new(false);
+    static ARMED_CHANGED: Condvar = Condvar::new();
+
+    /// Whether a crossterm terminal is reading input.
+    static TTY_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+    /// How long the watcher blocks in `poll(2)` before rechecking whether it is armed.
+    const WATCH_TIMEOUT_MS: i32 = 100;
+
+    /// Returns the read end of the signal pipe, creating it and the watcher on first use.
+    pub fn event_io_fd() -> Result<RawFd, Error> {
+        let ruby = magnus::Ruby::get().unwrap();
+        let fd = {
+            let mut signal = SIGNAL.lock().unwrap();
+            if let Some(signal) = signal.as_ref() {
+                signal.reader.as_raw_fd()
+            } else {
+                let (reader, writer) = std::io::pipe()
+                    .map_err(|e| Error::new(ruby.exception_io_error(), e.to_string()))?;
+                set_nonblocking(reader.as_raw_fd());
+                std::thread::Builder::new()
+                    .name("ratatui_ruby-event-watcher".to_string())
+                    .spawn(watch_terminal)
+                    .map_err(|e| Error::new(ruby.exception_runtime_error(), e.to_string()))?;
+                let fd = reader.as_raw_fd();
+                *signal = Some(Signal {
+                    reader,
+                    writer,
+                    raised: false,
+                });
+                fd
+            }
+        };
+        refresh();
+        Ok(fd)
+    }
+
+    /// Records whether a crossterm terminal is active, then recomputes readiness.
+    pub fn set_tty_active(active: bool) {
+        TTY_ACTIVE.store(active, Ordering::SeqCst);
+        refresh();
+    }
+
+    /// Recomputes readiness.
+    ///
+    /// Call this after anything that changes what `poll_event` would return next.
+    pub fn refresh() {
+        if SIGNAL.lock().unwrap().is_none() {
+            return;
+        }
+        let tty = TTY_ACTIVE.load(Ordering::SeqCst);
+        // Crossterm may already hold parsed events that are no longer visible on the fd.
+        let pending = crate::events::has_queued_events()
+            || (tty && ratatui::crossterm::event::poll(Duration::ZERO).unwrap_or(false));
+        set_raised(pending);
+        set_armed(tty && !pending);
+    }
+
+    fn set_raised(raised: bool) {
+        let mut guard = SIGNAL.lock().unwrap();
+        let Some(signal) = guard.as_mut() else {
+            return;
+        };
+        if signal.raised == raised {
+            return;
+        }
+        if raised {
+            let _ = signal.writer.write_all(&[1]);
+        } else {
+            // Non-blocking: if someone else drained the byte, there is nothing to do.
+            let _ = signal.reader.read(&mut [0]);
+        }
+        signal.raised = raised;
+    }
+
+    fn set_armed(armed: bool) {
+        *ARMED.lock().unwrap() = armed;
+        ARMED_CHANGED.notify_all();
+    }
+
+    fn watch_terminal() {
+        loop {
+            {
+                let mut armed = ARMED.lock().unwrap();
+                while !*armed {
+                    armed = ARMED_CHANGED.wait(armed).unwrap();
+                }
+            }
+            if terminal_readable(WATCH_TIMEOUT_MS) {
+                // Stay disarmed until `refresh` runs, or the fd would keep firing.
+                *ARMED.lock().unwrap() = false;
+                set_raised(true);
+            }
+        }
+    }
+
+    /// The fd crossterm reads from: stdin when it is a terminal, otherwise `/dev/tty`.
+    fn tty_fd() -> Option<RawFd> {
+        static TTY: OnceLock<Option<File>> = OnceLock::new();
+        if std::io::stdin().is_terminal() {
+            return Some(std::io::stdin().as_raw_fd());
+        }
+        TTY.get_or_init(|| File::open("/dev/tty").ok())
+            .as_ref()
+            .map(AsRawFd::as_raw_fd)
+    }
+
+    fn terminal_readable(timeout_ms: i32) -> bool {
+        let Some(fd) = tty_fd() else {
+            std::thread::sleep(Duration::from_millis(
+                WATCH_TIMEOUT_MS.unsigned_abs().into(),
+            ));
+            return false;
+        };
+        let mut pollfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `pollfd` is a valid array of one `pollfd` for the duration of the call.
+        let ready = unsafe { libc::poll(&raw mut pollfd, 1, timeout_ms) };
+        ready > 0 && pollfd.revents & libc::POLLIN != 0
+    }
+
+    fn set_nonblocking(fd: RawFd) {
+        // SAFETY: `fd` is an open descriptor owned by the pipe reader.
+        unsafe {
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use magnus::Error;
+
+    pub fn event_io_fd() -> Result<i32, Error> {
+        let ruby = magnus::Ruby::get().unwrap();
+        Err(Error::new(
+            ruby.exception_not_imp_error(),
+            "event_io is only available on Unix-like platforms",
+        ))
+    }
+
+    pub fn refresh() {}
+
+    pub fn set_tty_active(_active: bool) {}
+}
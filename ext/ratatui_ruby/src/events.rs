@@ -2,14 +2,34 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use magnus::{Error, IntoValue, TryConvert, Value};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 static EVENT_QUEUE: Mutex<Vec<ratatui::crossterm::event::Event>> = Mutex::new(Vec::new());
 
+/// Longest single crossterm poll while the GVL is released.
+///
+/// Crossterm cannot be woken from another thread, so the wait is sliced and the
+/// interrupt flag is checked between slices.
+const WAIT_SLICE: Duration = Duration::from_millis(20);
+
 #[allow(clippy::needless_pass_by_value)]
 pub fn inject_test_event(event_type: String, data: magnus::RHash) -> Result<(), Error> {
+    let event = parse_test_event(&event_type, data)?;
+    EVENT_QUEUE.lock().unwrap().push(event);
+    crate::event_source::refresh();
+    Ok(())
+}
+
+/// Builds a crossterm event from the type and data `inject_test_event` takes.
+pub fn parse_test_event(
+    event_type: &str,
+    data: magnus::RHash,
+) -> Result<ratatui::crossterm::event::Event, Error> {
     let ruby = magnus::Ruby::get().unwrap();
-    let event = match event_type.as_str() {
+    let event = match event_type {
         "key" => parse_key_event(data, &ruby)?,
         "mouse" => parse_mouse_event(data, &ruby)?,
         "resize" => parse_resize_event(data, &ruby)?,
@@ -23,9 +43,7 @@ pub fn inject_test_event(event_type: String, data: magnus::RHash) -> Result<(),
             ))
         }
     };
-
-    EVENT_QUEUE.lock().unwrap().push(event);
-    Ok(())
+    Ok(event)
 }
 
 /// Parses a `snake_case` string to `MediaKeyCode`.
@@ -75,11 +93,41 @@ fn parse_modifier_key(s: &str) -> Option<ratatui::crossterm::event::ModifierKeyC
     }
 }
 
+/// Reads the optional `modifiers` array shared by key and mouse events.
+fn parse_modifiers(
+    data: magnus::RHash,
+    ruby: &magnus::Ruby,
+) -> Result<ratatui::crossterm::event::KeyModifiers, Error> {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    let mut modifiers = KeyModifiers::empty();
+    if let Some(mods_val) = data.get(ruby.to_symbol("modifiers")) {
+        let mods: Vec<String> = Vec::try_convert(mods_val)?;
+        for m in mods {
+            modifiers |= match m.as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" => KeyModifiers::SUPER,
+                "hyper" => KeyModifiers::HYPER,
+                "meta" => KeyModifiers::META,
+                _ => {
+                    return Err(Error::new(
+                        ruby.exception_arg_error(),
+                        format!("Unknown modifier: {m}"),
+                    ))
+                }
+            };
+        }
+    }
+    Ok(modifiers)
+}
+
 fn parse_key_event(
     data: magnus::RHash,
     ruby: &magnus::Ruby,
 ) -> Result<ratatui::crossterm::event::Event, Error> {
-    use ratatui::crossterm::event::KeyCode;
+    use ratatui::crossterm::event::{KeyCode, KeyEventKind, KeyEventState};
 
     let code_val: Value = data
         .get(ruby.to_symbol("code"))
@@ -144,21 +192,46 @@ fn parse_key_event(
         }
     };
 
-    let mut modifiers = ratatui::crossterm::event::KeyModifiers::empty();
-    if let Some(mods_val) = data.get(ruby.to_symbol("modifiers")) {
-        let mods: Vec<String> = Vec::try_convert(mods_val)?;
-        for m in mods {
-            match m.as_str() {
-                "ctrl" => modifiers |= ratatui::crossterm::event::KeyModifiers::CONTROL,
-                "alt" => modifiers |= ratatui::crossterm::event::KeyModifiers::ALT,
-                "shift" => modifiers |= ratatui::crossterm::event::KeyModifiers::SHIFT,
-                _ => {}
+    let modifiers = parse_modifiers(data, ruby)?;
+
+    let kind = match data.get(ruby.to_symbol("action")) {
+        Some(action_val) => {
+            let action: String = String::try_convert(action_val)?;
+            match action.as_str() {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => {
+                    return Err(Error::new(
+                        ruby.exception_arg_error(),
+                        format!("Unknown key action: {action}"),
+                    ))
+                }
             }
         }
+        None => KeyEventKind::Press,
+    };
+
+    let mut state = KeyEventState::empty();
+    if let Some(state_val) = data.get(ruby.to_symbol("state")) {
+        let names: Vec<String> = Vec::try_convert(state_val)?;
+        for name in names {
+            state |= match name.as_str() {
+                "keypad" => KeyEventState::KEYPAD,
+                "caps_lock" => KeyEventState::CAPS_LOCK,
+                "num_lock" => KeyEventState::NUM_LOCK,
+                _ => {
+                    return Err(Error::new(
+                        ruby.exception_arg_error(),
+                        format!("Unknown key state: {name}"),
+                    ))
+                }
+            };
+        }
     }
 
     Ok(ratatui::crossterm::event::Event::Key(
-        ratatui::crossterm::event::KeyEvent::new(code, modifiers),
+        ratatui::crossterm::event::KeyEvent::new_with_kind_and_state(code, modifiers, kind, state),
     ))
 }
 
@@ -209,18 +282,7 @@ fn parse_mouse_event(
         }
     };
 
-    let mut modifiers = ratatui::crossterm::event::KeyModifiers::empty();
-    if let Some(mods_val) = data.get(ruby.to_symbol("modifiers")) {
-        let mods: Vec<String> = Vec::try_convert(mods_val)?;
-        for m in mods {
-            match m.as_str() {
-                "ctrl" => modifiers |= ratatui::crossterm::event::KeyModifiers::CONTROL,
-                "alt" => modifiers |= ratatui::crossterm::event::KeyModifiers::ALT,
-                "shift" => modifiers |= ratatui::crossterm::event::KeyModifiers::SHIFT,
-                _ => {}
-            }
-        }
-    }
+    let modifiers = parse_modifiers(data, ruby)?;
 
     Ok(ratatui::crossterm::event::Event::Mouse(
         ratatui::crossterm::event::MouseEvent {
@@ -271,9 +333,35 @@ fn parse_paste_event(
 
 pub fn clear_events() {
     EVENT_QUEUE.lock().unwrap().clear();
+    crate::event_source::refresh();
+}
+
+/// Queues events read from the terminal outside crossterm, after any already queued.
+pub fn queue_events(events: impl IntoIterator<Item = ratatui::crossterm::event::Event>) {
+    EVENT_QUEUE.lock().unwrap().extend(events);
+}
+
+/// Whether injected events are waiting to be returned by `poll_event`.
+pub fn has_queued_events() -> bool {
+    !EVENT_QUEUE.lock().unwrap().is_empty()
 }
 
 pub fn poll_event(ruby: &magnus::Ruby, timeout_val: Option<f64>) -> Result<Value, Error> {
+    let event = next_event(ruby, timeout_val);
+    crate::event_source::refresh();
+    match event? {
+        Some(e) => {
+            crate::recording::event(&e);
+            handle_event(e)
+        }
+        None => Ok(ruby.qnil().into_value_with(ruby)),
+    }
+}
+
+fn next_event(
+    ruby: &magnus::Ruby,
+    timeout_val: Option<f64>,
+) -> Result<Option<ratatui::crossterm::event::Event>, Error> {
     let event = {
         let mut queue = EVENT_QUEUE.lock().unwrap();
         if queue.is_empty() {
@@ -283,8 +371,8 @@ pub fn poll_event(ruby: &magnus::Ruby, timeout_val: Option<f64>) -> Result<Value
         }
     };
 
-    if let Some(e) = event {
-        return handle_event(e);
+    if event.is_some() {
+        return Ok(event);
     }
 
     let is_test_mode = {
@@ -296,30 +384,119 @@ pub fn poll_event(ruby: &magnus::Ruby, timeout_val: Option<f64>) -> Result<Value
     };
 
     if is_test_mode {
-        return Ok(ruby.qnil().into_value_with(ruby));
+        return Ok(None);
     }
 
-    if let Some(secs) = timeout_val {
-        // Timed poll: wait up to the specified duration
-        let duration = std::time::Duration::from_secs_f64(secs);
-        if ratatui::crossterm::event::poll(duration)
-            .map_err(|e| Error::new(ruby.exception_runtime_error(), e.to_string()))?
+    let event = wait_for_event(ruby, timeout_val.map(Duration::from_secs_f64))?;
+    // Repeats and releases only mean something when the app asked for them.
+    if let Some(ratatui::crossterm::event::Event::Key(key)) = &event {
+        if key.kind != ratatui::crossterm::event::KeyEventKind::Press
+            && !crate::terminal::reports_key_event_types()
         {
-            let event = ratatui::crossterm::event::read()
-                .map_err(|e| Error::new(ruby.exception_runtime_error(), e.to_string()))?;
-            handle_event(event)
-        } else {
-            Ok(ruby.qnil().into_value_with(ruby))
+            return Ok(None);
+        }
+    }
+    Ok(event)
+}
+
+/// Result of one GVL-free wait on crossterm.
+enum Wait {
+    Event(ratatui::crossterm::event::Event),
+    TimedOut,
+    Interrupted,
+}
+
+/// Data shared with `wait_without_gvl`, which cannot touch Ruby objects.
+struct WaitArgs<'a> {
+    deadline: Option<Instant>,
+    /// Set by `unblock_wait` when Ruby needs this thread back (`Thread#raise`, Ctrl-C, exit).
+    ///
+    /// Each wait has its own flag, so unblocking one thread never ends or hides another
+    /// thread's wait.
+    interrupted: &'a AtomicBool,
+    outcome: Option<std::io::Result<Wait>>,
+}
+
+/// Waits for a terminal event with the GVL released, so other Ruby threads keep running.
+///
+/// `None` means the timeout expired. Pending Ruby interrupts are raised as soon as the
+/// wait is unblocked.
+fn wait_for_event(
+    ruby: &magnus::Ruby,
+    timeout: Option<Duration>,
+) -> Result<Option<ratatui::crossterm::event::Event>, Error> {
+    let interrupted = AtomicBool::new(false);
+    let mut args = WaitArgs {
+        deadline: timeout.map(|t| Instant::now() + t),
+        interrupted: &interrupted,
+        outcome: None,
+    };
+
+    loop {
+        interrupted.store(false, Ordering::SeqCst);
+        // SAFETY: `args` and `interrupted` outlive the call, and neither callback touches
+        // Ruby objects. `unblock_wait` only reads `interrupted` through a shared pointer.
+        // Ruby may skip the call when an interrupt is already pending, which leaves
+        // `outcome` as `None`.
+        unsafe {
+            rb_sys::rb_thread_call_without_gvl(
+                Some(wait_without_gvl),
+                std::ptr::from_mut(&mut args).cast::<c_void>(),
+                Some(unblock_wait),
+                std::ptr::from_ref(&interrupted).cast_mut().cast::<c_void>(),
+            );
+        }
+
+        // Raises Interrupt, Thread#raise exceptions, etc.
+        ruby.thread_check_ints()?;
+
+        match args.outcome.take() {
+            Some(Ok(Wait::Event(event))) => return Ok(Some(event)),
+            Some(Ok(Wait::TimedOut)) => return Ok(None),
+            Some(Ok(Wait::Interrupted)) | None => {}
+            Some(Err(e)) => return Err(Error::new(ruby.exception_runtime_error(), e.to_string())),
         }
-    } else {
-        // Blocking: wait indefinitely for an event
-        let event = ratatui::crossterm::event::read()
-            .map_err(|e| Error::new(ruby.exception_runtime_error(), e.to_string()))?;
-        handle_event(event)
     }
 }
 
-fn handle_event(event: ratatui::crossterm::event::Event) -> Result<Value, Error> {
+/// Runs without the GVL. Must not call into Ruby.
+unsafe extern "C" fn wait_without_gvl(data: *mut c_void) -> *mut c_void {
+    // SAFETY: `data` is the `WaitArgs` passed by `wait_for_event`, which is blocked
+    // in `rb_thread_call_without_gvl` until this function returns.
+    let args = unsafe { &mut *data.cast::<WaitArgs>() };
+    args.outcome = Some(wait_in_slices(args.deadline, args.interrupted));
+    std::ptr::null_mut()
+}
+
+/// Called by Ruby from another thread to unblock `wait_without_gvl`.
+unsafe extern "C" fn unblock_wait(data: *mut c_void) {
+    // SAFETY: `data` is the `interrupted` flag of the `wait_for_event` call being
+    // unblocked, which is still blocked in `rb_thread_call_without_gvl`.
+    let interrupted = unsafe { &*data.cast::<AtomicBool>() };
+    interrupted.store(true, Ordering::SeqCst);
+}
+
+fn wait_in_slices(deadline: Option<Instant>, interrupted: &AtomicBool) -> std::io::Result<Wait> {
+    loop {
+        if interrupted.load(Ordering::SeqCst) {
+            return Ok(Wait::Interrupted);
+        }
+        let slice = match deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .min(WAIT_SLICE),
+            None => WAIT_SLICE,
+        };
+        if ratatui::crossterm::event::poll(slice)? {
+            return ratatui::crossterm::event::read().map(Wait::Event);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Ok(Wait::TimedOut);
+        }
+    }
+}
+
+pub fn handle_event(event: ratatui::crossterm::event::Event) -> Result<Value, Error> {
     match event {
         ratatui::crossterm::event::Event::Key(key) => handle_key_event(key),
         ratatui::crossterm::event::Event::Mouse(event) => handle_mouse_event(event),
@@ -376,13 +553,27 @@ fn modifier_key_to_string(m: ratatui::crossterm::event::ModifierKeyCode) -> &'st
     }
 }
 
+/// Converts `KeyModifiers` to the names used by `Event::Key#modifiers` and `Event::Mouse#modifiers`.
+fn modifier_names(modifiers: ratatui::crossterm::event::KeyModifiers) -> Vec<&'static str> {
+    use ratatui::crossterm::event::KeyModifiers;
+    [
+        (KeyModifiers::CONTROL, "ctrl"),
+        (KeyModifiers::ALT, "alt"),
+        (KeyModifiers::SHIFT, "shift"),
+        (KeyModifiers::SUPER, "super"),
+        (KeyModifiers::HYPER, "hyper"),
+        (KeyModifiers::META, "meta"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| modifiers.contains(*flag))
+    .map(|(_, name)| name)
+    .collect()
+}
+
 fn handle_key_event(key: ratatui::crossterm::event::KeyEvent) -> Result<Value, Error> {
-    use ratatui::crossterm::event::KeyCode;
+    use ratatui::crossterm::event::{KeyCode, KeyEventKind, KeyEventState};
 
     let ruby = magnus::Ruby::get().unwrap();
-    if key.kind != ratatui::crossterm::event::KeyEventKind::Press {
-        return Ok(ruby.qnil().into_value_with(&ruby));
-    }
     let hash = ruby.hash_new();
     hash.aset(ruby.to_symbol("type"), ruby.to_symbol("key"))?;
 
@@ -458,27 +649,30 @@ fn handle_key_event(key: ratatui::crossterm::event::KeyEvent) -> Result<Value, E
     hash.aset(ruby.to_symbol("code"), code)?;
     hash.aset(ruby.to_symbol("kind"), ruby.to_symbol(kind))?;
 
-    let mut modifiers = Vec::new();
-    if key
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::CONTROL)
-    {
-        modifiers.push("ctrl");
+    let modifiers = modifier_names(key.modifiers);
+    if !modifiers.is_empty() {
+        hash.aset(ruby.to_symbol("modifiers"), modifiers)?;
     }
-    if key
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::ALT)
-    {
-        modifiers.push("alt");
+
+    let action = match key.kind {
+        KeyEventKind::Press => "press",
+        KeyEventKind::Repeat => "repeat",
+        KeyEventKind::Release => "release",
+    };
+    hash.aset(ruby.to_symbol("action"), ruby.to_symbol(action))?;
+
+    let mut state = Vec::new();
+    if key.state.contains(KeyEventState::KEYPAD) {
+        state.push(ruby.to_symbol("keypad"));
     }
-    if key
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::SHIFT)
-    {
-        modifiers.push("shift");
+    if key.state.contains(KeyEventState::CAPS_LOCK) {
+        state.push(ruby.to_symbol("caps_lock"));
     }
-    if !modifiers.is_empty() {
-        hash.aset(ruby.to_symbol("modifiers"), modifiers)?;
+    if key.state.contains(KeyEventState::NUM_LOCK) {
+        state.push(ruby.to_symbol("num_lock"));
+    }
+    if !state.is_empty() {
+        hash.aset(ruby.to_symbol("state"), state)?;
     }
     Ok(hash.into_value_with(&ruby))
 }
@@ -525,25 +719,7 @@ fn handle_mouse_event(event: ratatui::crossterm::event::MouseEvent) -> Result<Va
     }
     hash.aset(ruby.to_symbol("x"), event.column)?;
     hash.aset(ruby.to_symbol("y"), event.row)?;
-    let mut modifiers = Vec::new();
-    if event
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::CONTROL)
-    {
-        modifiers.push("ctrl");
-    }
-    if event
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::ALT)
-    {
-        modifiers.push("alt");
-    }
-    if event
-        .modifiers
-        .contains(ratatui::crossterm::event::KeyModifiers::SHIFT)
-    {
-        modifiers.push("shift");
-    }
+    let modifiers = modifier_names(event.modifiers);
     hash.aset(ruby.to_symbol("modifiers"), modifiers)?;
     Ok(hash.into_value_with(&ruby))
 }
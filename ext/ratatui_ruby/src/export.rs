@@ -0,0 +1,408 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Serializes a buffer as ANSI text, HTML, or SVG.
+//!
+//! Rich snapshots, debugging output, and documentation screenshots all render from the
+//! same regions (runs of identically styled cells), so what one shows the others show.
+//! Named and indexed colors use xterm's default palette, and the default colors are
+//! xterm's gray on black. Blinking is not animated outside ANSI.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use std::fmt::Write;
+use unicode_width::UnicodeWidthStr;
+
+use crate::buffer::{regions, Region};
+use crate::color_depth;
+use crate::hyperlink::LinkTargets;
+
+const DEFAULT_FG: [u8; 3] = [229, 229, 229];
+const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+
+/// SVG cell size and font, in pixels. Monospace glyphs are about 0.6em wide.
+const CELL_WIDTH: u32 = 9;
+const CELL_HEIGHT: u32 = 18;
+const FONT_SIZE: u32 = 15;
+const BASELINE: u32 = 14;
+
+/// SGR parameters that turn each modifier on.
+const MODIFIER_CODES: [(Modifier, &str); 9] = [
+    (Modifier::BOLD, "1"),
+    (Modifier::DIM, "2"),
+    (Modifier::ITALIC, "3"),
+    (Modifier::UNDERLINED, "4"),
+    (Modifier::SLOW_BLINK, "5"),
+    (Modifier::RAPID_BLINK, "6"),
+    (Modifier::REVERSED, "7"),
+    (Modifier::HIDDEN, "8"),
+    (Modifier::CROSSED_OUT, "9"),
+];
+
+/// The look of a region, without its link.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Pen {
+    fg: Color,
+    bg: Color,
+    modifier: Modifier,
+}
+
+impl Pen {
+    const PLAIN: Pen = Pen {
+        fg: Color::Reset,
+        bg: Color::Reset,
+        modifier: Modifier::empty(),
+    };
+
+    fn of(region: &Region) -> Self {
+        Pen {
+            fg: region.fg,
+            bg: region.bg,
+            modifier: crate::hyperlink::strip(region.modifier),
+        }
+    }
+
+    /// Colors as shown, after `REVERSED`. `None` means the default color.
+    fn shown_colors(self) -> (Option<[u8; 3]>, Option<[u8; 3]>) {
+        let fg = color_depth::rgb(self.fg);
+        let bg = color_depth::rgb(self.bg);
+        if self.modifier.contains(Modifier::REVERSED) {
+            (
+                Some(bg.unwrap_or(DEFAULT_BG)),
+                Some(fg.unwrap_or(DEFAULT_FG)),
+            )
+        } else {
+            (fg, bg)
+        }
+    }
+
+    fn decorations(self) -> Option<&'static str> {
+        let underlined = self.modifier.contains(Modifier::UNDERLINED);
+        let crossed_out = self.modifier.contains(Modifier::CROSSED_OUT);
+        match (underlined, crossed_out) {
+            (true, true) => Some("underline line-through"),
+            (true, false) => Some("underline"),
+            (false, true) => Some("line-through"),
+            (false, false) => None,
+        }
+    }
+}
+
+fn link(buffer: &Buffer, links: &LinkTargets, region: &Region) -> Option<String> {
+    buffer
+        .cell((region.x, region.y))
+        .and_then(|cell| links.url(cell))
+}
+
+/// Splits regions into rows.
+fn rows(regions: &[Region]) -> impl Iterator<Item = &[Region]> {
+    regions.chunk_by(|a, b| a.y == b.y)
+}
+
+/// Returns the buffer as text with SGR escape sequences, one line per row.
+///
+/// Each line starts and ends with default colors, so lines can be printed on their
+/// own. Links become OSC 8 sequences, as the terminal backend writes them.
+pub fn to_ansi(buffer: &Buffer, links: &LinkTargets) -> String {
+    let regions = regions(buffer);
+    let mut out = String::new();
+    for row in rows(&regions) {
+        let mut pen = Pen::PLAIN;
+        let mut open_link: Option<String> = None;
+        for region in row {
+            let next = Pen::of(region);
+            out.push_str(&sgr(pen, next));
+            pen = next;
+            let url = link(buffer, links, region);
+            if url != open_link {
+                if open_link.is_some() {
+                    out.push_str("\x1b]8;;\x1b\\");
+                }
+                if let Some(url) = &url {
+                    let _ = write!(out, "\x1b]8;;{url}\x1b\\");
+                }
+                open_link = url;
+            }
+            out.push_str(&region.text);
+        }
+        if open_link.is_some() {
+            out.push_str("\x1b]8;;\x1b\\");
+        }
+        if pen != Pen::PLAIN {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Returns the shortest SGR sequence that changes `from` into `to`.
+fn sgr(from: Pen, to: Pen) -> String {
+    if from == to {
+        return String::new();
+    }
+    if to == Pen::PLAIN {
+        return "\x1b[0m".to_string();
+    }
+    let mut params = Vec::new();
+    let from = if from.modifier.difference(to.modifier).is_empty() {
+        from
+    } else {
+        params.push("0".to_string());
+        Pen::PLAIN
+    };
+    for (modifier, code) in MODIFIER_CODES {
+        if to.modifier.contains(modifier) && !from.modifier.contains(modifier) {
+            params.push(code.to_string());
+        }
+    }
+    if to.fg != from.fg {
+        params.push(color_code(to.fg, 30));
+    }
+    if to.bg != from.bg {
+        params.push(color_code(to.bg, 40));
+    }
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// SGR parameters for a color. `base` is 30 for foreground and 40 for background.
+fn color_code(color: Color, base: u8) -> String {
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Indexed(i) => format!("{};5;{i}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+        named => match color_depth::ansi16_index(named) {
+            Some(i @ 0..=7) => (base + i).to_string(),
+            Some(i) => (base + 60 + i - 8).to_string(),
+            None => (base + 9).to_string(),
+        },
+    }
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the buffer as a standalone HTML document with inline styles.
+pub fn to_html(buffer: &Buffer, links: &LinkTargets) -> String {
+    let regions = regions(buffer);
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n");
+    let _ = write!(
+        out,
+        "<body style=\"margin:0\">\n<pre style=\"margin:0;color:{};background-color:{};font-family:monospace;line-height:1.2\">",
+        hex(DEFAULT_FG),
+        hex(DEFAULT_BG)
+    );
+    for (i, row) in rows(&regions).enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for region in row {
+            let text = escape(&region.text);
+            let css = css(Pen::of(region));
+            let span = if css.is_empty() {
+                text
+            } else {
+                format!("<span style=\"{css}\">{text}</span>")
+            };
+            match link(buffer, links, region) {
+                Some(url) => {
+                    let _ = write!(
+                        out,
+                        "<a href=\"{}\" style=\"color:inherit;text-decoration:inherit\">{span}</a>",
+                        escape(&url)
+                    );
+                }
+                None => out.push_str(&span),
+            }
+        }
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn css(pen: Pen) -> String {
+    let (fg, bg) = pen.shown_colors();
+    let mut declarations = Vec::new();
+    if let Some(fg) = fg {
+        declarations.push(format!("color:{}", hex(fg)));
+    }
+    if let Some(bg) = bg {
+        declarations.push(format!("background-color:{}", hex(bg)));
+    }
+    if pen.modifier.contains(Modifier::BOLD) {
+        declarations.push("font-weight:bold".to_string());
+    }
+    if pen.modifier.contains(Modifier::ITALIC) {
+        declarations.push("font-style:italic".to_string());
+    }
+    if pen.modifier.contains(Modifier::DIM) {
+        declarations.push("opacity:0.5".to_string());
+    }
+    if let Some(decorations) = pen.decorations() {
+        declarations.push(format!("text-decoration:{decorations}"));
+    }
+    if pen.modifier.contains(Modifier::HIDDEN) {
+        declarations.push("visibility:hidden".to_string());
+    }
+    declarations.join(";")
+}
+
+/// Returns the buffer as an SVG image with one monospace cell per buffer cell.
+///
+/// Every character is placed at its cell's column, so box drawing and wide characters
+/// line up whatever the font's widths.
+pub fn to_svg(buffer: &Buffer, links: &LinkTargets) -> String {
+    let regions = regions(buffer);
+    let width = u32::from(buffer.area.width) * CELL_WIDTH;
+    let height = u32::from(buffer.area.height) * CELL_HEIGHT;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+    );
+    let _ = writeln!(
+        out,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        hex(DEFAULT_BG)
+    );
+    let _ = writeln!(
+        out,
+        "<g font-family=\"monospace\" font-size=\"{FONT_SIZE}\" fill=\"{}\" style=\"white-space:pre\">",
+        hex(DEFAULT_FG)
+    );
+    for region in &regions {
+        let pen = Pen::of(region);
+        let (fg, bg) = pen.shown_colors();
+        let x = u32::from(region.x - buffer.area.x) * CELL_WIDTH;
+        let y = u32::from(region.y - buffer.area.y) * CELL_HEIGHT;
+        if let Some(bg) = bg {
+            let _ = writeln!(
+                out,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"{CELL_HEIGHT}\" fill=\"{}\"/>",
+                u32::from(region.width) * CELL_WIDTH,
+                hex(bg)
+            );
+        }
+        let decorations = pen.decorations();
+        if pen.modifier.contains(Modifier::HIDDEN)
+            || (decorations.is_none() && region.text.trim().is_empty())
+        {
+            continue;
+        }
+        let mut text = format!(
+            "<text x=\"{}\" y=\"{}\"",
+            char_positions(buffer, region),
+            y + BASELINE
+        );
+        if let Some(fg) = fg {
+            let _ = write!(text, " fill=\"{}\"", hex(fg));
+        }
+        if pen.modifier.contains(Modifier::BOLD) {
+            text.push_str(" font-weight=\"bold\"");
+        }
+        if pen.modifier.contains(Modifier::ITALIC) {
+            text.push_str(" font-style=\"italic\"");
+        }
+        if pen.modifier.contains(Modifier::DIM) {
+            text.push_str(" opacity=\"0.5\"");
+        }
+        if let Some(decorations) = decorations {
+            let _ = write!(text, " text-decoration=\"{decorations}\"");
+        }
+        let _ = write!(text, ">{}</text>", escape(&region.text));
+        match link(buffer, links, region) {
+            Some(url) => {
+                let _ = writeln!(out, "<a href=\"{}\">{text}</a>", escape(&url));
+            }
+            None => {
+                let _ = writeln!(out, "{text}");
+            }
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+/// The x coordinate of every character in a region, each at the column of its cell.
+fn char_positions(buffer: &Buffer, region: &Region) -> String {
+    let mut positions = Vec::new();
+    let mut x = region.x;
+    while x < region.x + region.width {
+        let symbol = buffer[(x, region.y)].symbol();
+        let left = u32::from(x - buffer.area.x) * CELL_WIDTH;
+        positions.extend(symbol.chars().map(|_| left.to_string()));
+        x += u16::try_from(symbol.width().max(1)).unwrap_or(1);
+    }
+    positions.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_to_ansi_changes_only_what_differs() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buffer.set_string(0, 0, "a", Style::default().red().bold());
+        buffer.set_string(1, 0, "b", Style::default().blue().bold());
+        buffer.set_string(2, 0, "c", Style::default().blue());
+        assert_eq!(
+            to_ansi(&buffer, &LinkTargets::Shared),
+            "\x1b[1;31ma\x1b[34mb\x1b[0;34mc\x1b[0m \n"
+        );
+    }
+
+    #[test]
+    fn test_to_ansi_keeps_plain_rows_plain() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+        buffer.set_string(0, 0, "中a", Style::default());
+        assert_eq!(to_ansi(&buffer, &LinkTargets::Shared), "中a\n   \n");
+    }
+
+    #[test]
+    fn test_color_codes() {
+        assert_eq!(color_code(Color::Gray, 30), "37");
+        assert_eq!(color_code(Color::White, 40), "107");
+        assert_eq!(color_code(Color::Indexed(42), 30), "38;5;42");
+        assert_eq!(color_code(Color::Rgb(1, 2, 3), 40), "48;2;1;2;3");
+        assert_eq!(color_code(Color::Reset, 40), "49");
+    }
+
+    #[test]
+    fn test_to_html_escapes_and_reverses() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(0, 0, "<", Style::default().reversed());
+        let html = to_html(&buffer, &LinkTargets::Shared);
+        assert!(html
+            .contains("<span style=\"color:#000000;background-color:#e5e5e5\">&lt;</span> </pre>"));
+    }
+
+    #[test]
+    fn test_to_svg_places_characters_on_the_grid() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "中a", Style::default().on_blue());
+        let svg = to_svg(&buffer, &LinkTargets::Shared);
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"27\" height=\"18\" fill=\"#0000ee\"/>"));
+        assert!(svg.contains("<text x=\"0 18\" y=\"14\">中a</text>"));
+    }
+}
@@ -17,8 +17,9 @@
 //!
 //! The `'static` lifetime is a lie, but a safe one within these constraints.
 
+use crate::cursor;
+use crate::registry::{self, ResolvedStateful};
 use crate::rendering;
-use crate::widgets;
 use magnus::{prelude::*, Error, Value};
 use ratatui::layout::Rect;
 use ratatui::Frame;
@@ -185,25 +186,26 @@ impl RubyFrame {
         // SAFETY: The frame pointer is valid for the duration of the draw callback.
         let frame = unsafe { (*self.inner.get()).as_mut() };
 
-        // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
-        let widget_class = unsafe { widget.class().name() }.into_owned();
-        // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
-        let state_class = unsafe { state.class().name() }.into_owned();
-
-        match (widget_class.as_str(), state_class.as_str()) {
-            ("RatatuiRuby::Widgets::List", "RatatuiRuby::ListState") => {
-                widgets::list::render_stateful(frame, rect, widget, state)
-            }
-            ("RatatuiRuby::Widgets::Table", "RatatuiRuby::TableState") => {
-                widgets::table::render_stateful(frame, rect, widget, state)
+        match registry::resolve_stateful(widget, state)? {
+            Some(ResolvedStateful::Native(render)) => render(frame, rect, widget, state),
+            Some(ResolvedStateful::Registered(renderer)) => {
+                crate::buffer::lend(frame, |buffer| {
+                    renderer.funcall::<_, _, Value>("call", (widget, area, buffer, state))
+                })?;
+                Ok(())
             }
-            ("RatatuiRuby::Widgets::Scrollbar", "RatatuiRuby::ScrollbarState") => {
-                widgets::scrollbar::render_stateful(frame, rect, widget, state)
+            None => {
+                // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
+                let widget_class = unsafe { widget.class().name() }.into_owned();
+                // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
+                let state_class = unsafe { state.class().name() }.into_owned();
+                Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!(
+                        "Unsupported widget/state combination: {widget_class} with {state_class}"
+                    ),
+                ))
             }
-            _ => Err(Error::new(
-                ruby.exception_arg_error(),
-                format!("Unsupported widget/state combination: {widget_class} with {state_class}"),
-            )),
         }
     }
 
@@ -222,7 +224,30 @@ impl RubyFrame {
         // SAFETY: The frame pointer is valid for the duration of the draw callback.
         // ensure_active() above guarantees we're still in the callback.
         let frame = unsafe { (*self.inner.get()).as_mut() };
-        frame.set_cursor_position((x, y));
+        cursor::set_position(frame, (x, y));
+        Ok(())
+    }
+
+    /// Sets the cursor shape for this and later frames.
+    ///
+    /// Ratatui has no equivalent; the style is sent with crossterm's `SetCursorStyle`
+    /// after the frame is drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - `:default`, `:block`, `:underline`, or `:bar`
+    /// * `blinking` - Whether the cursor blinks
+    pub fn set_cursor_style(&self, shape: Value, blinking: bool) -> Result<(), Error> {
+        self.ensure_active()?;
+        let shape = cursor::parse_shape(shape)?;
+        cursor::request_style(cursor::CursorStyle { shape, blinking });
+        Ok(())
+    }
+
+    /// Hides the cursor for this frame, even if its position was set.
+    pub fn hide_cursor(&self) -> Result<(), Error> {
+        self.ensure_active()?;
+        cursor::request_hidden();
         Ok(())
     }
 }
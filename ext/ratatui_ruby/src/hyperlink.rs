@@ -0,0 +1,480 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! OSC 8 hyperlinks on spans.
+//!
+//! Writing the escape sequences into cell symbols would break Ratatui's width
+//! calculation, which counts the URL as visible text. Instead, a linked span carries a
+//! small link ID in modifier bits Ratatui does not use. Cells keep their plain symbol
+//! and width, and a changed link changes the cell, so the diff repaints it.
+//!
+//! `HyperlinkBackend` strips the ID before handing cells to crossterm and wraps each run
+//! of linked cells in OSC 8 sequences. The test backend keeps the ID, so `get_cell_at`
+//! can report the target.
+//!
+//! There are 127 IDs, shared by every terminal in the process. An ID is reused only
+//! once no cell on any screen carries it. Links beyond that render as plain text.
+//! Buffers copied off a screen take their URLs along in `LinkTargets`, so however many
+//! of them Ruby keeps, they hold no shared IDs.
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::{Buffer, Cell},
+    layout::{Position, Size},
+    style::{Modifier, Style},
+};
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+const LINK_SHIFT: u16 = 9;
+const LINK_MASK: u16 = 0xFE00;
+const MAX_LINKS: usize = 127;
+
+// Link IDs live in modifier bits Ratatui does not define. Stop the build if it ever does.
+const _: () = assert!(
+    Modifier::all().bits() & LINK_MASK == 0,
+    "Ratatui uses the modifier bits that carry link IDs"
+);
+
+struct Links {
+    /// URL for each ID. Index 0 is unused: it means "no link".
+    urls: Vec<Option<String>>,
+    /// IDs carried by cells currently on each screen, as bit sets keyed by screen.
+    on_screen: Vec<(usize, u128)>,
+    /// IDs handed out since the last draw, as a bit set.
+    in_frame: u128,
+    /// Frames being rendered, on any thread.
+    frames: usize,
+}
+
+static LINKS: Mutex<Links> = Mutex::new(Links {
+    urls: Vec::new(),
+    on_screen: Vec::new(),
+    in_frame: 0,
+    frames: 0,
+});
+
+/// The screen of the module-level terminal. Terminal and buffer objects use others.
+pub const DEFAULT_SCREEN: usize = 0;
+
+static NEXT_SCREEN: AtomicUsize = AtomicUsize::new(DEFAULT_SCREEN + 1);
+
+/// Hands out a screen, so links on it are not reused while it shows them.
+pub fn new_screen() -> usize {
+    NEXT_SCREEN.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Returns the style that links a span to `url`, or the empty style if all IDs are in use.
+pub fn style_for(url: &str) -> Style {
+    let mut links = LINKS.lock().unwrap();
+    let id = links.find(url).or_else(|| links.allocate(url));
+    match id {
+        Some(id) => {
+            links.in_frame |= 1 << id;
+            Style::default().add_modifier(Modifier::from_bits_retain(id << LINK_SHIFT))
+        }
+        None => Style::default(),
+    }
+}
+
+impl Links {
+    fn find(&self, url: &str) -> Option<u16> {
+        let id = self.urls.iter().position(|u| u.as_deref() == Some(url))?;
+        u16::try_from(id).ok()
+    }
+
+    fn allocate(&mut self, url: &str) -> Option<u16> {
+        if self.urls.is_empty() {
+            self.urls.resize(MAX_LINKS + 1, None);
+        }
+        let busy = self
+            .on_screen
+            .iter()
+            .fold(self.in_frame, |busy, (_, ids)| busy | ids);
+        let id = (1..=MAX_LINKS).find(|&id| busy & (1 << id) == 0)?;
+        self.urls[id] = Some(url.to_string());
+        u16::try_from(id).ok()
+    }
+
+    fn url(&self, id: u16) -> Option<&str> {
+        self.urls.get(usize::from(id))?.as_deref()
+    }
+
+    fn record(&mut self, screen: usize, buffer: &Buffer) {
+        let on_screen = buffer
+            .content
+            .iter()
+            .fold(0u128, |ids, cell| ids | (1 << link_id(cell.modifier)))
+            & !1;
+        match self.on_screen.iter_mut().find(|(s, _)| *s == screen) {
+            Some((_, ids)) => *ids = on_screen,
+            None => self.on_screen.push((screen, on_screen)),
+        }
+    }
+}
+
+fn link_id(modifier: Modifier) -> u16 {
+    (modifier.bits() & LINK_MASK) >> LINK_SHIFT
+}
+
+/// Returns `modifier` without the link ID.
+pub fn strip(modifier: Modifier) -> Modifier {
+    Modifier::from_bits_retain(modifier.bits() & !LINK_MASK)
+}
+
+/// Returns the link target of a cell drawn by the test backend.
+fn url_at(cell: &Cell) -> Option<String> {
+    let id = link_id(cell.modifier);
+    if id == 0 {
+        return None;
+    }
+    LINKS.lock().unwrap().url(id).map(str::to_string)
+}
+
+/// Keeps the IDs a frame hands out while it renders.
+///
+/// Frames nest, such as a `Terminal#draw` inside a `RatatuiRuby.draw` block, and Ruby
+/// threads take turns building theirs. Handed-out IDs stay reserved until every frame
+/// has finished, so one frame finishing never frees IDs already written into another.
+pub struct FrameLinks {
+    screen: usize,
+}
+
+impl FrameLinks {
+    /// Starts a frame that will be drawn on `screen`.
+    pub fn start(screen: usize) -> Self {
+        LINKS.lock().unwrap().frames += 1;
+        Self { screen }
+    }
+
+    /// Records which IDs are on the screen after the draw, so the rest can be reused.
+    pub fn finish(self, buffer: &Buffer) {
+        LINKS.lock().unwrap().record(self.screen, buffer);
+    }
+}
+
+impl Drop for FrameLinks {
+    fn drop(&mut self) {
+        let mut links = LINKS.lock().unwrap_or_else(PoisonError::into_inner);
+        links.frames -= 1;
+        if links.frames == 0 {
+            links.in_frame = 0;
+        }
+    }
+}
+
+/// Frees the IDs on `screen` when its terminal or offscreen render goes away.
+pub fn forget_screen(screen: usize) {
+    let mut links = LINKS.lock().unwrap_or_else(PoisonError::into_inner);
+    links.on_screen.retain(|(s, _)| *s != screen);
+}
+
+/// Where the URLs of a buffer's link IDs are looked up.
+pub enum LinkTargets {
+    /// The shared table, for buffers on a screen.
+    Shared,
+    /// A table of the buffer's own, indexed by ID, for buffers copied off a screen.
+    Own(Vec<Option<String>>),
+}
+
+impl LinkTargets {
+    /// Copies the URLs of the links in `buffer` out of the shared table.
+    pub fn copy(buffer: &Buffer) -> Self {
+        let links = LINKS.lock().unwrap();
+        let mut urls = vec![None; MAX_LINKS + 1];
+        for cell in &buffer.content {
+            let id = link_id(cell.modifier);
+            if id != 0 && urls[usize::from(id)].is_none() {
+                urls[usize::from(id)] = links.url(id).map(str::to_string);
+            }
+        }
+        LinkTargets::Own(urls)
+    }
+
+    /// Returns the link target of a cell.
+    pub fn url(&self, cell: &Cell) -> Option<String> {
+        match self {
+            LinkTargets::Shared => url_at(cell),
+            LinkTargets::Own(urls) => urls.get(usize::from(link_id(cell.modifier)))?.clone(),
+        }
+    }
+
+    /// Moves the links of styles made by `style_for` into this table, for cells about
+    /// to be written into `buffer`.
+    ///
+    /// A link is dropped if every ID is carried by a cell of the buffer.
+    pub fn adopt<'a>(&mut self, styles: impl IntoIterator<Item = &'a mut Style>, buffer: &Buffer) {
+        let LinkTargets::Own(urls) = self else {
+            return;
+        };
+        let mut busy = buffer
+            .content
+            .iter()
+            .fold(0u128, |ids, cell| ids | (1 << link_id(cell.modifier)));
+        let links = LINKS.lock().unwrap();
+        for style in styles {
+            let shared = link_id(style.add_modifier);
+            if shared == 0 {
+                continue;
+            }
+            style.add_modifier = strip(style.add_modifier);
+            let Some(url) = links.url(shared) else {
+                continue;
+            };
+            let id = urls
+                .iter()
+                .position(|u| u.as_deref() == Some(url))
+                .or_else(|| (1..=MAX_LINKS).find(|&id| busy & (1 << id) == 0));
+            if let Some(id) = id.and_then(|id| u16::try_from(id).ok()) {
+                busy |= 1 << id;
+                urls[usize::from(id)] = Some(url.to_string());
+                *style = style.add_modifier(Modifier::from_bits_retain(id << LINK_SHIFT));
+            }
+        }
+    }
+}
+
+/// Forgets the default screen's links when the terminal is restored or replaced.
+pub fn reset() {
+    let mut links = LINKS.lock().unwrap_or_else(PoisonError::into_inner);
+    links.on_screen.retain(|(s, _)| *s != DEFAULT_SCREEN);
+    // Frames still rendering may have written their IDs already.
+    if links.frames == 0 {
+        links.in_frame = 0;
+        if links.on_screen.is_empty() {
+            links.urls.clear();
+        }
+    }
+}
+
+/// Wraps a backend, turning link IDs in cells into OSC 8 sequences.
+///
+/// It also opens synchronized updates (mode 2026) for `draw`. Ratatui renders a frame
+/// before writing it, so opening the update when the cells arrive means the terminal
+/// holds output only while the frame is written, not while Ruby code builds it.
+pub struct HyperlinkBackend<B> {
+    inner: B,
+    update: Update,
+}
+
+/// Where the backend is in a synchronized update.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Update {
+    Closed,
+    /// Opens when the next cells are drawn.
+    Pending,
+    Open,
+}
+
+impl<B: Backend<Error = io::Error> + Write> HyperlinkBackend<B> {
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            update: Update::Closed,
+        }
+    }
+
+    /// Opens a synchronized update now.
+    pub fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        ratatui::crossterm::execute!(
+            self.inner,
+            ratatui::crossterm::terminal::BeginSynchronizedUpdate
+        )?;
+        self.update = Update::Open;
+        Ok(())
+    }
+
+    /// Opens a synchronized update right before the next cells are drawn.
+    pub fn begin_synchronized_update_on_draw(&mut self) {
+        self.update = Update::Pending;
+    }
+
+    /// Closes the synchronized update, if one was opened.
+    pub fn end_synchronized_update(&mut self) -> io::Result<()> {
+        let open = self.update == Update::Open;
+        self.update = Update::Closed;
+        if open {
+            ratatui::crossterm::execute!(
+                self.inner,
+                ratatui::crossterm::terminal::EndSynchronizedUpdate
+            )?;
+        }
+        Ok(())
+    }
+
+    fn draw_run(&mut self, run: &[(u16, u16, Cow<'_, Cell>)], url: Option<&str>) -> io::Result<()> {
+        if let Some(url) = url {
+            write!(self.inner, "\x1b]8;;{url}\x1b\\")?;
+        }
+        self.inner
+            .draw(run.iter().map(|(x, y, cell)| (*x, *y, cell.as_ref())))?;
+        if url.is_some() {
+            write!(self.inner, "\x1b]8;;\x1b\\")?;
+        }
+        Ok(())
+    }
+}
+
+impl<B: Backend + Write> Write for HyperlinkBackend<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Write::flush(&mut self.inner)
+    }
+}
+
+impl<B> Backend for HyperlinkBackend<B>
+where
+    B: Backend<Error = io::Error> + Write,
+{
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.update == Update::Pending {
+            ratatui::crossterm::queue!(
+                self.inner,
+                ratatui::crossterm::terminal::BeginSynchronizedUpdate
+            )?;
+            self.update = Update::Open;
+        }
+        let mut run: Vec<(u16, u16, Cow<'a, Cell>)> = Vec::new();
+        let mut run_link = 0;
+        for (x, y, cell) in content {
+            let link = link_id(cell.modifier);
+            if link != run_link && !run.is_empty() {
+                let url = LINKS.lock().unwrap().url(run_link).map(str::to_string);
+                self.draw_run(&run, url.as_deref())?;
+                run.clear();
+            }
+            run_link = link;
+            if link == 0 {
+                run.push((x, y, Cow::Borrowed(cell)));
+            } else {
+                let mut plain = cell.clone();
+                plain.modifier = strip(cell.modifier);
+                run.push((x, y, Cow::Owned(plain)));
+            }
+        }
+        if !run.is_empty() {
+            let url = LINKS.lock().unwrap().url(run_link).map(str::to_string);
+            self.draw_run(&run, url.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_link_id_round_trip() {
+        let style = Style::default()
+            .add_modifier(Modifier::BOLD)
+            .add_modifier(Modifier::from_bits_retain(5 << LINK_SHIFT));
+        assert_eq!(link_id(style.add_modifier), 5);
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_link_bits_are_unused_by_ratatui() {
+        assert_eq!(Modifier::all().bits() & LINK_MASK, 0);
+        assert_eq!(usize::from(LINK_MASK >> LINK_SHIFT), MAX_LINKS);
+    }
+
+    #[test]
+    fn test_copied_targets_outlive_shared_ids() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(0, 0, "a", style_for("https://a.example"));
+        let mut targets = LinkTargets::copy(&buffer);
+        let mut style = style_for("https://b.example");
+        targets.adopt([&mut style], &buffer);
+        buffer.set_string(1, 0, "b", style);
+        reset();
+        assert_eq!(
+            targets.url(&buffer.content[0]).as_deref(),
+            Some("https://a.example")
+        );
+        assert_eq!(
+            targets.url(&buffer.content[1]).as_deref(),
+            Some("https://b.example")
+        );
+    }
+
+    #[test]
+    fn test_nested_frame_keeps_outer_ids() {
+        let outer = FrameLinks::start(new_screen());
+        let outer_style = style_for("https://outer.example");
+        let inner_screen = new_screen();
+        FrameLinks::start(inner_screen).finish(&Buffer::empty(Rect::new(0, 0, 1, 1)));
+        let later_style = style_for("https://later.example");
+        assert_ne!(
+            link_id(outer_style.add_modifier),
+            link_id(later_style.add_modifier)
+        );
+        forget_screen(inner_screen);
+        drop(outer);
+    }
+
+    #[test]
+    fn test_link_id_survives_styling_cells() {
+        let link = Modifier::from_bits_retain(5 << LINK_SHIFT);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(0, 0, "ab", Style::default().add_modifier(link));
+        buffer.set_style(
+            Rect::new(0, 0, 2, 1),
+            Style::default().add_modifier(Modifier::BOLD),
+        );
+        let cell = &buffer.content[0];
+        assert_eq!(link_id(cell.modifier), 5);
+        assert_eq!(strip(cell.modifier), Modifier::BOLD);
+    }
+}
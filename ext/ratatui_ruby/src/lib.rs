@@ -10,27 +10,85 @@
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::module_name_repetitions)]
 
+mod buffer;
+mod color_depth;
+mod cursor;
+mod draw_stats;
+mod event_source;
 mod events;
+mod export;
 mod frame;
+mod hyperlink;
+mod osc;
+mod output;
+mod recording;
+mod registry;
 mod rendering;
+mod ruby_terminal;
 mod string_width;
 mod style;
+mod teardown;
 mod terminal;
+mod terminal_colors;
 mod text;
 mod widgets;
 
+use draw_stats::DrawStats;
 use frame::RubyFrame;
-use magnus::{function, method, Error, Module, Object, Ruby, Value};
-use terminal::{init_terminal, restore_terminal, TERMINAL};
+use magnus::{function, method, Error, Module, Object, Ruby, TryConvert, Value};
+use std::time::{Duration, Instant};
+use terminal::{init_terminal, restore_terminal, terminal_error, TERMINAL};
 
-/// Draw to the terminal.
+/// What rendering a frame produced besides its buffer.
+struct Rendered {
+    /// Time native widgets spent rendering.
+    render_duration: Duration,
+    /// What the frame asked of the cursor.
+    cursor: cursor::Request,
+}
+
+/// Renders one frame from Ruby: yields a `RubyFrame` to the block, or renders `tree` to the full area.
 ///
-/// Supports two calling conventions:
-/// - Legacy: `RatatuiRuby.draw(tree)` - Renders a widget tree to the full terminal area
-/// - New: `RatatuiRuby.draw { |frame| ... }` - Yields a Frame for explicit widget placement
-fn draw(args: &[Value]) -> Result<(), Error> {
-    let ruby = Ruby::get().unwrap();
+/// Colors are mapped down to `depth` once rendering finishes.
+fn render_frame(
+    ruby: &Ruby,
+    f: &mut ratatui::Frame<'_>,
+    tree: Option<Value>,
+    depth: color_depth::ColorDepth,
+) -> Result<Rendered, Error> {
+    let timer = draw_stats::RenderTimer::start();
+    let cursor = cursor::FrameRequest::start();
+    if let Some(tree_value) = tree {
+        // Legacy API: render tree to full area
+        rendering::render_node(f, f.area(), tree_value)?;
+        draw_stats::timed(|| color_depth::downsample(f.buffer_mut(), depth));
+        return Ok(Rendered {
+            render_duration: timer.finish(),
+            cursor: cursor.finish(),
+        });
+    }
+
+    // New API: yield RubyFrame to block
+    // Create validity flag — set to true while the block is executing
+    let active = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
 
+    let ruby_frame = RubyFrame::new(f, active.clone());
+    let result = ruby.yield_value::<_, Value>(ruby_frame);
+
+    // Invalidate frame immediately after block returns
+    // This prevents use-after-free if user stored the frame object
+    active.store(false, std::sync::atomic::Ordering::Relaxed);
+    result?;
+    draw_stats::timed(|| color_depth::downsample(f.buffer_mut(), depth));
+    Ok(Rendered {
+        render_duration: timer.finish(),
+        cursor: cursor.finish(),
+    })
+}
+
+/// Parses the arguments of `draw`: an optional tree, which must be given if and only
+/// if there is no block.
+fn draw_args(ruby: &Ruby, args: &[Value]) -> Result<Option<Value>, Error> {
     // Parse arguments: check for optional tree argument
     let tree: Option<Value> = if args.is_empty() {
         None
@@ -60,50 +118,87 @@ fn draw(args: &[Value]) -> Result<(), Error> {
             "Must provide either a tree or a block to draw",
         ));
     }
+    Ok(tree)
+}
+
+/// Draw to the terminal.
+///
+/// Supports two calling conventions:
+/// - Legacy: `RatatuiRuby.draw(tree)` - Renders a widget tree to the full terminal area
+/// - New: `RatatuiRuby.draw { |frame| ... }` - Yields a Frame for explicit widget placement
+///
+/// Returns the frame's statistics as a Hash, or `nil` without a terminal.
+fn draw(args: &[Value]) -> Result<Option<magnus::RHash>, Error> {
+    let ruby = Ruby::get().unwrap();
+    let tree = draw_args(&ruby, args)?;
+    let depth = color_depth::get();
 
     let mut term_lock = TERMINAL.lock().unwrap();
     let mut render_error: Option<Error> = None;
+    let mut build_duration = Duration::ZERO;
+    let mut render_duration = Duration::ZERO;
+    let mut cursor_request = cursor::Request::default();
 
     // Helper closure to execute the draw callback logic for either terminal type
     let mut draw_callback = |f: &mut ratatui::Frame<'_>| {
-        if block_given {
-            // New API: yield RubyFrame to block
-            // Create validity flag — set to true while the block is executing
-            let active = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
-
-            let ruby_frame = RubyFrame::new(f, active.clone());
-            if let Err(e) = ruby.yield_value::<_, Value>(ruby_frame) {
-                render_error = Some(e);
-            }
-
-            // Invalidate frame immediately after block returns
-            // This prevents use-after-free if user stored the frame object
-            active.store(false, std::sync::atomic::Ordering::Relaxed);
-        } else if let Some(tree_value) = tree {
-            // Legacy API: render tree to full area
-            if let Err(e) = rendering::render_node(f, f.area(), tree_value) {
-                render_error = Some(e);
+        let started = Instant::now();
+        match teardown::catch_panic(|| render_frame(&ruby, f, tree, depth)) {
+            Ok(rendered) => {
+                render_duration += rendered.render_duration;
+                cursor_request = rendered.cursor;
             }
+            Err(e) => render_error = Some(e),
         }
+        build_duration += started.elapsed();
     };
 
+    let mut stats = None;
     if let Some(wrapper) = term_lock.as_mut() {
-        match wrapper {
-            terminal::TerminalWrapper::Crossterm(term) => {
-                let module = ruby.define_module("RatatuiRuby")?;
-                let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-                let error_class = error_base.const_get("Terminal")?;
-                term.draw(&mut draw_callback)
-                    .map_err(|e| Error::new(error_class, e.to_string()))?;
-            }
-            terminal::TerminalWrapper::Test(term) => {
-                let module = ruby.define_module("RatatuiRuby")?;
-                let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-                let error_class = error_base.const_get("Terminal")?;
-                term.draw(&mut draw_callback)
-                    .map_err(|e| Error::new(error_class, e.to_string()))?;
-            }
+        let links = hyperlink::FrameLinks::start(hyperlink::DEFAULT_SCREEN);
+        let started = Instant::now();
+        // Forget output since the last frame, such as a title change.
+        wrapper.take_counts();
+        let synchronized = terminal::begin_synchronized_update_on_draw(wrapper);
+        let drawn = match wrapper {
+            terminal::TerminalWrapper::Crossterm(term) => term
+                .draw(&mut draw_callback)
+                .map(|completed| {
+                    links.finish(completed.buffer);
+                    completed.count
+                })
+                .map_err(|e| e.to_string()),
+            terminal::TerminalWrapper::Test(term) => term
+                .draw(&mut draw_callback)
+                .map(|completed| {
+                    links.finish(completed.buffer);
+                    completed.count
+                })
+                .inspect(|_| recording::frame(term.backend().buffer()))
+                .map_err(|e| e.to_string()),
         }
+        .and_then(|count| {
+            cursor::apply(wrapper, cursor_request).map_err(|e| e.to_string())?;
+            Ok(count)
+        });
+        // End the update even if drawing failed, or the terminal would keep holding output.
+        let ended = if synchronized {
+            terminal::end_synchronized_update(wrapper)
+        } else {
+            Ok(())
+        };
+        let duration = started.elapsed();
+        let count = drawn.map_err(terminal_error)?;
+        ended.map_err(|e| terminal_error(e.to_string()))?;
+        let (bytes_written, cells_changed) = wrapper.take_counts();
+        stats = Some(DrawStats {
+            frame_count: count.wrapping_add(1),
+            duration,
+            convert_duration: build_duration.saturating_sub(render_duration),
+            render_duration,
+            output_duration: duration.saturating_sub(build_duration),
+            cells_changed,
+            bytes_written,
+        });
     } else {
         eprintln!("Terminal is None!");
     }
@@ -112,6 +207,87 @@ fn draw(args: &[Value]) -> Result<(), Error> {
         return Err(e);
     }
 
+    let Some(stats) = stats else {
+        return Ok(None);
+    };
+    draw_stats::record(stats);
+    draw_stats::to_hash(&stats).map(Some)
+}
+
+/// Inserts lines above an inline viewport.
+///
+/// Supports the same calling conventions as `draw`, after a leading height:
+/// - `RatatuiRuby.insert_before(height, tree)` - Renders a widget tree into the inserted lines
+/// - `RatatuiRuby.insert_before(height) { |frame| ... }` - Yields a Frame sized to the inserted lines
+///
+/// The lines are rendered offscreen first, so the terminal lock is not held while Ruby code runs.
+/// Ratatui ignores insertions unless the viewport is inline.
+fn insert_before(args: &[Value]) -> Result<(), Error> {
+    let ruby = Ruby::get().unwrap();
+
+    let (height_val, tree) = match args {
+        [height] => (*height, None),
+        [height, tree] => (*height, Some(*tree)),
+        _ => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!(
+                    "wrong number of arguments (given {}, expected 1..2)",
+                    args.len()
+                ),
+            ))
+        }
+    };
+    let height = u16::try_convert(height_val)?;
+    if tree.is_none() && !ruby.block_given() {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            "Must provide either a tree or a block to insert_before",
+        ));
+    }
+
+    let width = match TERMINAL.lock().unwrap().as_mut() {
+        Some(terminal::TerminalWrapper::Crossterm(term)) => term.get_frame().area().width,
+        Some(terminal::TerminalWrapper::Test(term)) => term.get_frame().area().width,
+        None => return Err(terminal_error("Terminal is not initialized")),
+    };
+
+    let mut offscreen = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height))
+        .map_err(|e| terminal_error(e.to_string()))?;
+    let depth = color_depth::get();
+    let mut render_error: Option<Error> = None;
+    offscreen
+        .draw(|f| {
+            if let Err(e) = teardown::catch_panic(|| render_frame(&ruby, f, tree, depth)) {
+                render_error = Some(e);
+            }
+        })
+        .map_err(|e| terminal_error(e.to_string()))?;
+    if let Some(e) = render_error {
+        return Err(e);
+    }
+    let lines = offscreen.backend().buffer().clone();
+
+    if let Some(wrapper) = TERMINAL.lock().unwrap().as_mut() {
+        let synchronized = terminal::begin_synchronized_update(wrapper)
+            .map_err(|e| terminal_error(e.to_string()))?;
+        let inserted = match wrapper {
+            terminal::TerminalWrapper::Crossterm(term) => term
+                .insert_before(height, |buf| buf.merge(&lines))
+                .map_err(|e| e.to_string()),
+            terminal::TerminalWrapper::Test(term) => term
+                .insert_before(height, |buf| buf.merge(&lines))
+                .map_err(|e| e.to_string()),
+        };
+        let ended = if synchronized {
+            terminal::end_synchronized_update(wrapper)
+        } else {
+            Ok(())
+        };
+        inserted.map_err(terminal_error)?;
+        ended.map_err(|e| terminal_error(e.to_string()))?;
+    }
+
     Ok(())
 }
 
@@ -119,10 +295,39 @@ fn draw(args: &[Value]) -> Result<(), Error> {
 fn init() -> Result<(), Error> {
     let ruby = magnus::Ruby::get().unwrap();
     let m = ruby.define_module("RatatuiRuby")?;
+    teardown::install();
 
-    m.define_module_function("_init_terminal", function!(init_terminal, 2))?;
+    m.define_module_function("_init_terminal", function!(init_terminal, 8))?;
+    m.define_module_function(
+        "_set_mouse_capture",
+        function!(terminal::set_mouse_capture, 1),
+    )?;
+    m.define_module_function(
+        "_set_focus_events",
+        function!(terminal::set_focus_events, 1),
+    )?;
+    m.define_module_function(
+        "_set_bracketed_paste",
+        function!(terminal::set_bracketed_paste, 1),
+    )?;
+    m.define_module_function("_input_modes", function!(terminal::input_modes, 0))?;
     m.define_module_function("restore_terminal", function!(restore_terminal, 0))?;
+    m.define_module_function("color_depth", function!(color_depth::color_depth, 0))?;
+    m.define_module_function(
+        "_last_draw_stats",
+        function!(draw_stats::last_draw_stats, 0),
+    )?;
+    m.define_module_function(
+        "_query_terminal_colors",
+        function!(terminal_colors::query_terminal_colors, 2),
+    )?;
+    m.define_module_function(
+        "_suspend_terminal",
+        function!(terminal::suspend_terminal, 0),
+    )?;
+    m.define_module_function("_resume_terminal", function!(terminal::resume_terminal, 0))?;
     m.define_module_function("_draw", function!(draw, -1))?;
+    m.define_module_function("_insert_before", function!(insert_before, -1))?;
 
     // Register Frame class
     let frame_class = m.define_class("Frame", ruby.class_object())?;
@@ -136,28 +341,53 @@ fn init() -> Result<(), Error> {
         "set_cursor_position",
         method!(RubyFrame::set_cursor_position, 2),
     )?;
+    frame_class.define_method("_set_cursor_style", method!(RubyFrame::set_cursor_style, 2))?;
+    frame_class.define_method("hide_cursor", method!(RubyFrame::hide_cursor, 0))?;
     m.define_module_function("_poll_event", function!(events::poll_event, 1))?;
     m.define_module_function("inject_test_event", function!(events::inject_test_event, 2))?;
     m.define_module_function("clear_events", function!(events::clear_events, 0))?;
+    m.define_module_function("_event_io_fd", function!(event_source::event_io_fd, 0))?;
 
     // Register State classes
     widgets::list_state::register(&ruby, m)?;
     widgets::table_state::register(&ruby, m)?;
     widgets::scrollbar_state::register(&ruby, m)?;
 
+    // Register Terminal objects
+    ruby_terminal::register(&ruby, m)?;
+
+    // Register standalone buffers
+    buffer::register(&ruby, m)?;
+
+    // Register widget renderers from Ruby
+    registry::register(m)?;
+
     // Test backend helpers
     m.define_module_function(
-        "init_test_terminal",
-        function!(terminal::init_test_terminal, 2),
+        "_init_test_terminal",
+        function!(terminal::init_test_terminal, 4),
     )?;
     m.define_module_function(
         "get_buffer_content",
         function!(terminal::get_buffer_content, 0),
     )?;
+    m.define_module_function("get_buffer", function!(terminal::get_buffer, 0))?;
+    m.define_module_function("_start_recording", function!(recording::start_recording, 3))?;
+    m.define_module_function("stop_recording", function!(recording::stop_recording, 0))?;
+    m.define_module_function("recording?", function!(recording::is_recording, 0))?;
     m.define_module_function(
         "get_cursor_position",
         function!(terminal::get_cursor_position, 0),
     )?;
+    m.define_module_function("get_cursor_style", function!(cursor::get_cursor_style, 0))?;
+    m.define_module_function("set_title", function!(osc::set_title, 1))?;
+    m.define_module_function("restore_title", function!(osc::restore_title, 0))?;
+    m.define_module_function("_copy_to_clipboard", function!(osc::copy_to_clipboard, 2))?;
+    m.define_module_function("get_title", function!(osc::get_title, 0))?;
+    m.define_module_function(
+        "get_clipboard_writes",
+        function!(osc::get_clipboard_writes, 0),
+    )?;
     m.define_module_function("_get_cell_at", function!(terminal::get_cell_at, 2))?;
     m.define_module_function("resize_terminal", function!(terminal::resize_terminal, 2))?;
 
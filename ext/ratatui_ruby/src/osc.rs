@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Window title and clipboard, set with OSC escape sequences.
+//!
+//! Both go through the crossterm backend, so they never land in the middle of a frame.
+//! The test backend records them instead, for assertions.
+//!
+//! The title is pushed onto the terminal's title stack before the first change, and
+//! popped by `restore_title` or `restore_terminal`. Terminals without a title stack
+//! ignore both.
+
+use magnus::{prelude::*, Error, Value};
+use ratatui::crossterm::{terminal::SetTitle, Command};
+use std::fmt;
+use std::io;
+use std::sync::{Mutex, PoisonError};
+
+use crate::terminal::{terminal_error, TerminalWrapper, TERMINAL};
+
+struct State {
+    title: Option<String>,
+    /// Whether the user's own title is saved on the title stack.
+    title_pushed: bool,
+    /// Text written to the clipboard by the test backend, oldest first.
+    clipboard: Vec<String>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    title: None,
+    title_pushed: false,
+    clipboard: Vec::new(),
+});
+
+/// Saves the current title on the terminal's title stack (XTWINOPS 22).
+struct PushTitle;
+
+impl Command for PushTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[22;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Restores the title saved by `PushTitle` (XTWINOPS 23).
+struct PopTitle;
+
+impl Command for PopTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[23;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes text to a clipboard selection (OSC 52).
+struct SetClipboard<'a> {
+    selection: char,
+    text: &'a str,
+}
+
+impl Command for SetClipboard<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "\x1b]52;{};{}\x07",
+            self.selection,
+            base64(self.text.as_bytes())
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        let n = (u32::from(chunk[0]) << 16) | (u32::from(b1) << 8) | u32::from(b2);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Runs `f` with the terminal and the recorded state, locked in that order.
+fn with_terminal(
+    f: impl FnOnce(&mut TerminalWrapper, &mut State) -> io::Result<()>,
+) -> Result<(), Error> {
+    let mut term_lock = TERMINAL.lock().unwrap();
+    let Some(wrapper) = term_lock.as_mut() else {
+        return Err(terminal_error("Terminal is not initialized"));
+    };
+    let mut state = STATE.lock().unwrap();
+    f(wrapper, &mut state).map_err(|e| terminal_error(e.to_string()))
+}
+
+pub fn set_title(title: String) -> Result<(), Error> {
+    with_terminal(|wrapper, state| {
+        if let TerminalWrapper::Crossterm(t) = wrapper {
+            if !state.title_pushed {
+                ratatui::crossterm::execute!(t.backend_mut(), PushTitle)?;
+                crate::teardown::set_title_pushed(true);
+            }
+            ratatui::crossterm::execute!(t.backend_mut(), SetTitle(&title))?;
+        }
+        state.title_pushed = true;
+        state.title = Some(title);
+        Ok(())
+    })
+}
+
+pub fn restore_title() -> Result<(), Error> {
+    with_terminal(|wrapper, state| {
+        pop_title(wrapper, state);
+        Ok(())
+    })
+}
+
+fn pop_title(wrapper: &mut TerminalWrapper, state: &mut State) {
+    if state.title_pushed {
+        if let TerminalWrapper::Crossterm(t) = wrapper {
+            let _ = ratatui::crossterm::execute!(t.backend_mut(), PopTitle);
+            crate::teardown::set_title_pushed(false);
+        }
+    }
+    state.title_pushed = false;
+    state.title = None;
+}
+
+/// Puts the user's title back and forgets the recorded state. Called by `restore_terminal`.
+pub fn restore(wrapper: &mut TerminalWrapper) {
+    let mut state = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    pop_title(wrapper, &mut state);
+    state.clipboard.clear();
+}
+
+/// Forgets the recorded state when a test terminal replaces the previous one.
+pub fn reset() {
+    let mut state = STATE.lock().unwrap_or_else(PoisonError::into_inner);
+    state.title = None;
+    state.title_pushed = false;
+    state.clipboard.clear();
+}
+
+/// Writes `text` to the clipboard `selection` (`:clipboard` or `:primary`).
+pub fn copy_to_clipboard(text: String, selection: Value) -> Result<(), Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let selection: magnus::Symbol = magnus::Symbol::try_convert(selection)?;
+    let selection = match selection.to_string().as_str() {
+        "clipboard" => 'c',
+        "primary" => 'p',
+        other => {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("Unknown clipboard selection: {other}"),
+            ))
+        }
+    };
+    with_terminal(|wrapper, state| {
+        match wrapper {
+            TerminalWrapper::Crossterm(t) => ratatui::crossterm::execute!(
+                t.backend_mut(),
+                SetClipboard {
+                    selection,
+                    text: &text
+                }
+            )?,
+            TerminalWrapper::Test(_) => state.clipboard.push(text),
+        }
+        Ok(())
+    })
+}
+
+/// Returns the title set with `set_title`, or `nil`.
+pub fn get_title() -> Option<String> {
+    STATE.lock().unwrap().title.clone()
+}
+
+/// Returns the text the test backend received for the clipboard, oldest first.
+pub fn get_clipboard_writes() -> Vec<String> {
+    STATE.lock().unwrap().clipboard.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_clipboard_sequence() {
+        let mut out = String::new();
+        SetClipboard {
+            selection: 'c',
+            text: "hi",
+        }
+        .write_ansi(&mut out)
+        .unwrap();
+        assert_eq!(out, "\x1b]52;c;aGk=\x07");
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Where the crossterm terminal writes.
+//!
+//! Tools like pickers print their result to stdout, which is often a pipe. They draw
+//! on the controlling terminal instead, or on a descriptor the app opened itself.
+//! Crossterm already reads input and the window size from `/dev/tty` when stdin is
+//! not a terminal, so only output needs redirecting.
+
+use magnus::{prelude::*, Error, Value};
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+#[cfg(unix)]
+use crate::terminal::terminal_error;
+
+#[derive(Clone, Debug)]
+pub enum Output {
+    Stdout,
+    /// `/dev/tty`, or a duplicate of a descriptor the app passed in, so the app can
+    /// close its own IO without affecting the terminal.
+    File(Arc<File>),
+}
+
+impl PartialEq for Output {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Output::Stdout, Output::Stdout) => true,
+            (Output::File(a), Output::File(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Output {}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout => io::stdout().write(buf),
+            Output::File(file) => file.as_ref().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout => io::stdout().flush(),
+            Output::File(file) => file.as_ref().flush(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Output {
+    /// The descriptor crash handlers write to.
+    pub fn raw_fd(&self) -> std::os::fd::RawFd {
+        use std::os::fd::AsRawFd;
+        match self {
+            Output::Stdout => libc::STDOUT_FILENO,
+            Output::File(file) => file.as_raw_fd(),
+        }
+    }
+}
+
+/// Converts the Ruby `output:` option: `:stdout`, `:tty`, or an Integer file descriptor.
+pub fn parse(value: Value) -> Result<Output, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if value.is_nil() {
+        return Ok(Output::Stdout);
+    }
+    if let Some(symbol) = magnus::Symbol::from_value(value) {
+        return match symbol.to_string().as_str() {
+            "stdout" => Ok(Output::Stdout),
+            "tty" => open_tty(),
+            other => Err(Error::new(
+                ruby.exception_arg_error(),
+                format!("Unknown output: {other}"),
+            )),
+        };
+    }
+    let fd = i32::try_convert(value)?;
+    duplicate(fd)
+}
+
+#[cfg(unix)]
+fn open_tty() -> Result<Output, Error> {
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .map_err(|e| terminal_error(format!("Cannot open /dev/tty: {e}")))?;
+    Ok(Output::File(Arc::new(file)))
+}
+
+#[cfg(unix)]
+fn duplicate(fd: i32) -> Result<Output, Error> {
+    use std::os::fd::BorrowedFd;
+    let ruby = magnus::Ruby::get().unwrap();
+    // SAFETY: `fcntl(F_GETFD)` only checks whether `fd` is open.
+    if fd < 0 || unsafe { libc::fcntl(fd, libc::F_GETFD) } < 0 {
+        return Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("Invalid output file descriptor: {fd}"),
+        ));
+    }
+    // SAFETY: `fd` is open, and the caller's IO keeps it open for the duration of this call.
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    let owned = borrowed
+        .try_clone_to_owned()
+        .map_err(|e| terminal_error(e.to_string()))?;
+    Ok(Output::File(Arc::new(File::from(owned))))
+}
+
+#[cfg(not(unix))]
+fn open_tty() -> Result<Output, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    Err(Error::new(
+        ruby.exception_not_imp_error(),
+        "output: :tty is only available on Unix-like platforms",
+    ))
+}
+
+#[cfg(not(unix))]
+fn duplicate(_fd: i32) -> Result<Output, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    Err(Error::new(
+        ruby.exception_not_imp_error(),
+        "Drawing to an IO is only available on Unix-like platforms",
+    ))
+}
@@ -0,0 +1,331 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Recording the module-level terminal as an asciicast v2 file.
+//!
+//! The crossterm backend writes through `CountingWriter`, which hands every byte to
+//! `capture`. Each flush ends a frame and becomes one `"o"` event, so the file replays
+//! exactly what the terminal received. Recording starts by clearing the terminal, so
+//! the first frame is drawn in full.
+//!
+//! The test backend has no output stream. Each `draw` becomes one `"o"` event that
+//! repaints the whole screen, and time advances a fixed step per event, so recording
+//! the same test twice produces the same file.
+
+use magnus::{Error, Ruby};
+use ratatui::{buffer::Buffer, crossterm::event::Event};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::terminal::{terminal_error, TerminalWrapper, TERMINAL};
+
+/// Seconds between events recorded from the test backend.
+const TEST_STEP: f64 = 0.1;
+
+enum Clock {
+    Real(Instant),
+    Steps(u32),
+}
+
+struct Recording {
+    file: BufWriter<File>,
+    clock: Clock,
+    input: bool,
+    size: (u16, u16),
+    /// Output written since the last flush.
+    pending: Vec<u8>,
+    /// The first write error, raised by `stop_recording`.
+    error: Option<io::Error>,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+impl Recording {
+    fn now(&mut self) -> f64 {
+        match &mut self.clock {
+            Clock::Real(started) => started.elapsed().as_secs_f64(),
+            Clock::Steps(steps) => {
+                *steps += 1;
+                f64::from(*steps) * TEST_STEP
+            }
+        }
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) {
+        let time = self.now();
+        let line = format!("[{time:.6}, \"{code}\", {}]\n", json_string(data));
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    /// Writes pending output as one event, keeping a trailing partial UTF-8 character.
+    fn flush_output(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let text = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.to_string(),
+            Err(e) if e.error_len().is_none() => {
+                let valid = e.valid_up_to();
+                let text = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+                self.pending.drain(..valid);
+                if !text.is_empty() {
+                    self.write_event("o", &text);
+                }
+                return;
+            }
+            Err(_) => String::from_utf8_lossy(&self.pending).into_owned(),
+        };
+        self.pending.clear();
+        self.write_event("o", &text);
+    }
+}
+
+/// Starts recording the module-level terminal to `path`.
+pub fn start_recording(path: String, input: bool, title: Option<String>) -> Result<(), Error> {
+    let mut term_lock = TERMINAL.lock().unwrap();
+    let Some(wrapper) = term_lock.as_mut() else {
+        return Err(terminal_error("Terminal is not initialized"));
+    };
+    let mut recording = RECORDING.lock().unwrap();
+    if recording.is_some() {
+        return Err(terminal_error("Already recording"));
+    }
+    let (size, clock) = match wrapper {
+        TerminalWrapper::Crossterm(terminal) => {
+            let size = terminal.size().map_err(|e| terminal_error(e.to_string()))?;
+            ((size.width, size.height), Clock::Real(Instant::now()))
+        }
+        TerminalWrapper::Test(terminal) => {
+            let area = terminal.backend().buffer().area;
+            ((area.width, area.height), Clock::Steps(0))
+        }
+    };
+    let file =
+        File::create(&path).map_err(|e| terminal_error(format!("Cannot record to {path}: {e}")))?;
+
+    let mut header = format!(
+        "{{\"version\": 2, \"width\": {}, \"height\": {}",
+        size.0, size.1
+    );
+    if let Clock::Real(_) = clock {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let _ = write!(header, ", \"timestamp\": {timestamp}");
+        if let Ok(term) = std::env::var("TERM") {
+            let _ = write!(header, ", \"env\": {{\"TERM\": {}}}", json_string(&term));
+        }
+    }
+    if let Some(title) = title {
+        let _ = write!(header, ", \"title\": {}", json_string(&title));
+    }
+    header.push_str("}\n");
+
+    let mut file = BufWriter::new(file);
+    file.write_all(header.as_bytes())
+        .map_err(|e| terminal_error(e.to_string()))?;
+    *recording = Some(Recording {
+        file,
+        clock,
+        input,
+        size,
+        pending: Vec::new(),
+        error: None,
+    });
+    drop(recording);
+
+    // Forget what is on screen, so the next draw repaints everything into the recording.
+    if let TerminalWrapper::Crossterm(terminal) = wrapper {
+        terminal
+            .clear()
+            .map_err(|e| terminal_error(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Stops recording and closes the file. Does nothing when not recording.
+pub fn stop_recording() -> Result<(), Error> {
+    let Some(mut recording) = RECORDING.lock().unwrap().take() else {
+        return Ok(());
+    };
+    recording.flush_output();
+    let flushed = recording.file.flush();
+    let error = recording.error.take().map_or(flushed, Err);
+    error.map_err(|e| {
+        let ruby = Ruby::get().unwrap();
+        Error::new(ruby.exception_io_error(), e.to_string())
+    })
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.lock().unwrap().is_some()
+}
+
+/// Stops recording when the terminal is restored, ignoring write errors.
+pub fn reset() {
+    let recording = RECORDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(mut recording) = recording {
+        recording.flush_output();
+        let _ = recording.file.flush();
+    }
+}
+
+/// Collects bytes the crossterm backend wrote.
+pub fn capture(bytes: &[u8]) {
+    if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+        recording.pending.extend_from_slice(bytes);
+    }
+}
+
+/// Ends a frame of crossterm output.
+pub fn flush() {
+    if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+        recording.flush_output();
+    }
+}
+
+/// Records a frame drawn by the test backend as a repaint of the whole screen.
+pub fn frame(buffer: &Buffer) {
+    let mut lock = RECORDING.lock().unwrap();
+    let Some(recording) = lock.as_mut() else {
+        return;
+    };
+    let ansi = crate::export::to_ansi(buffer, &crate::hyperlink::LinkTargets::Shared);
+    let screen = ansi.trim_end_matches('\n').replace('\n', "\r\n");
+    recording.write_event("o", &format!("\x1b[H\x1b[2J{screen}"));
+}
+
+/// Records a resize, and the input of key and paste events when asked to.
+pub fn event(event: &Event) {
+    let mut lock = RECORDING.lock().unwrap();
+    let Some(recording) = lock.as_mut() else {
+        return;
+    };
+    if let Event::Resize(width, height) = *event {
+        resize_locked(recording, width, height);
+    } else if recording.input {
+        if let Some(input) = input_bytes(event) {
+            recording.flush_output();
+            recording.write_event("i", &input);
+        }
+    }
+}
+
+/// Records that the test terminal was resized.
+pub fn resize(width: u16, height: u16) {
+    if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+        resize_locked(recording, width, height);
+    }
+}
+
+fn resize_locked(recording: &mut Recording, width: u16, height: u16) {
+    if recording.size != (width, height) {
+        recording.size = (width, height);
+        recording.flush_output();
+        recording.write_event("r", &format!("{width}x{height}"));
+    }
+}
+
+/// Returns what the terminal would have sent for a key press or paste.
+fn input_bytes(event: &Event) -> Option<String> {
+    use ratatui::crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+    let key = match event {
+        Event::Key(key) if key.kind != KeyEventKind::Release => key,
+        Event::Paste(text) => return Some(format!("\x1b[200~{text}\x1b[201~")),
+        _ => return None,
+    };
+    let bytes = match key.code {
+        KeyCode::Char(c)
+            if key.modifiers.contains(KeyModifiers::CONTROL) && c.is_ascii_alphabetic() =>
+        {
+            char::from(u8::try_from(c).ok()? & 0x1f).to_string()
+        }
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "\r".to_string(),
+        KeyCode::Tab => "\t".to_string(),
+        KeyCode::BackTab => "\x1b[Z".to_string(),
+        KeyCode::Backspace => "\x7f".to_string(),
+        KeyCode::Esc => "\x1b".to_string(),
+        KeyCode::Up => "\x1b[A".to_string(),
+        KeyCode::Down => "\x1b[B".to_string(),
+        KeyCode::Right => "\x1b[C".to_string(),
+        KeyCode::Left => "\x1b[D".to_string(),
+        KeyCode::Home => "\x1b[H".to_string(),
+        KeyCode::End => "\x1b[F".to_string(),
+        KeyCode::PageUp => "\x1b[5~".to_string(),
+        KeyCode::PageDown => "\x1b[6~".to_string(),
+        KeyCode::Insert => "\x1b[2~".to_string(),
+        KeyCode::Delete => "\x1b[3~".to_string(),
+        KeyCode::F(n @ 1..=4) => format!("\x1bO{}", char::from(b'P' + n - 1)),
+        KeyCode::F(n @ 5..=12) => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][usize::from(n - 5)];
+            format!("\x1b[{code}~")
+        }
+        _ => return None,
+    };
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        Some(format!("\x1b{bytes}"))
+    } else {
+        Some(bytes)
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 || c == '\u{7f}' => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(
+            json_string("a\"b\\\x1b[0m\n"),
+            "\"a\\\"b\\\\\\u001b[0m\\n\""
+        );
+    }
+
+    #[test]
+    fn test_input_bytes() {
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            input_bytes(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)).as_deref(),
+            Some("\x03")
+        );
+        assert_eq!(
+            input_bytes(&key(KeyCode::Char('x'), KeyModifiers::ALT)).as_deref(),
+            Some("\x1bx")
+        );
+        assert_eq!(
+            input_bytes(&key(KeyCode::F(5), KeyModifiers::NONE)).as_deref(),
+            Some("\x1b[15~")
+        );
+        assert_eq!(input_bytes(&Event::FocusGained), None);
+    }
+}
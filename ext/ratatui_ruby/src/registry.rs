@@ -0,0 +1,346 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Finding out how to render a widget.
+//!
+//! Built-in widgets are known by class name. Ruby code, including other gems, can
+//! register renderers for its own classes and modules with `register_widget` and
+//! `register_stateful_widget`. Those are called with the widget, its area, and the
+//! frame's buffer, like custom widgets with `render(area, buffer)`.
+//!
+//! Lookups walk the widget's ancestors, nearest first, so a subclass renders like its
+//! parent unless it has a renderer of its own. At each ancestor, a registered renderer
+//! comes first, then a built-in one, then a `render` method defined there. Apps can
+//! therefore replace how a built-in widget draws, and a subclass that defines `render`
+//! draws itself.
+//!
+//! There is no Rust entry point for registering renderers. Ratatui's types have no
+//! stable ABI between separately compiled extensions, so a native gem registers a
+//! Ruby callable, which may call into its own extension and draw through the buffer.
+//!
+//! Each class is looked up once, and what was found is kept until another renderer is
+//! registered. A `render` method added to a class after its instances were drawn is
+//! not noticed until then. Objects with a singleton class, and those answering `render`
+//! through `method_missing`, are asked for their `render` method every time.
+
+use std::borrow::Cow;
+
+use magnus::{
+    function, prelude::*, value::Lazy, Error, RArray, RClass, RHash, RModule, Ruby, Symbol, Value,
+};
+use ratatui::{layout::Rect, Frame};
+
+use crate::widgets;
+
+type Renderer = fn(&mut Frame, Rect, Value) -> Result<(), Error>;
+type StatefulRenderer = fn(&mut Frame, Rect, Value, Value) -> Result<(), Error>;
+
+const WIDGETS: &[(&str, Renderer)] = &[
+    (
+        "RatatuiRuby::Widgets::Paragraph",
+        widgets::paragraph::render,
+    ),
+    ("RatatuiRuby::Widgets::Clear", widgets::clear::render),
+    ("RatatuiRuby::Widgets::Cursor", widgets::cursor::render),
+    ("RatatuiRuby::Widgets::Overlay", widgets::overlay::render),
+    ("RatatuiRuby::Widgets::Center", widgets::center::render),
+    ("RatatuiRuby::Layout::Layout", widgets::layout::render),
+    ("RatatuiRuby::Widgets::List", widgets::list::render),
+    ("RatatuiRuby::Widgets::Gauge", widgets::gauge::render),
+    (
+        "RatatuiRuby::Widgets::LineGauge",
+        widgets::line_gauge::render,
+    ),
+    ("RatatuiRuby::Widgets::Table", widgets::table::render),
+    ("RatatuiRuby::Widgets::Block", widgets::block::render),
+    ("RatatuiRuby::Widgets::Tabs", widgets::tabs::render),
+    (
+        "RatatuiRuby::Widgets::Scrollbar",
+        widgets::scrollbar::render,
+    ),
+    ("RatatuiRuby::Widgets::BarChart", widgets::barchart::render),
+    ("RatatuiRuby::Widgets::Canvas", widgets::canvas::render),
+    ("RatatuiRuby::Widgets::Calendar", widgets::calendar::render),
+    (
+        "RatatuiRuby::Widgets::Sparkline",
+        widgets::sparkline::render,
+    ),
+    ("RatatuiRuby::Widgets::Chart", widgets::chart::render),
+    (
+        "RatatuiRuby::Widgets::LineChart",
+        widgets::chart::render_line_chart,
+    ),
+    ("RatatuiRuby::LineChart", widgets::chart::render_line_chart),
+    ("RatatuiRuby::Widgets::RatatuiLogo", |frame, area, node| {
+        widgets::ratatui_logo::render(frame, area, node);
+        Ok(())
+    }),
+    (
+        "RatatuiRuby::Widgets::RatatuiMascot",
+        widgets::ratatui_mascot::render_ratatui_mascot,
+    ),
+];
+
+const STATEFUL_WIDGETS: &[(&str, &str, StatefulRenderer)] = &[
+    (
+        "RatatuiRuby::Widgets::List",
+        "RatatuiRuby::ListState",
+        widgets::list::render_stateful,
+    ),
+    (
+        "RatatuiRuby::Widgets::Table",
+        "RatatuiRuby::TableState",
+        widgets::table::render_stateful,
+    ),
+    (
+        "RatatuiRuby::Widgets::Scrollbar",
+        "RatatuiRuby::ScrollbarState",
+        widgets::scrollbar::render_stateful,
+    ),
+];
+
+/// Renderers registered from Ruby, keyed by class or module.
+static RENDERERS: Lazy<RHash> = Lazy::new(|ruby| ruby.hash_new());
+
+/// Stateful renderers registered from Ruby: a Hash of state class to renderer, keyed
+/// by widget class or module.
+static STATEFUL_RENDERERS: Lazy<RHash> = Lazy::new(|ruby| ruby.hash_new());
+
+/// What `resolve` found for each class: `[:native, index]`, `[:registered, renderer]`,
+/// `[:method, arity]`, or `[:instance]` when each object must be asked for `render`.
+static RESOLVED: Lazy<RHash> = Lazy::new(|ruby| ruby.hash_new());
+
+/// What `resolve_stateful` found: a Hash of state class to `[:native, index]`,
+/// `[:registered, renderer]`, or `[:none]`, keyed by widget class.
+static RESOLVED_STATEFUL: Lazy<RHash> = Lazy::new(|ruby| ruby.hash_new());
+
+/// How to render a widget.
+pub enum Resolved {
+    /// A built-in renderer.
+    Native(Renderer),
+    /// A renderer registered from Ruby, called with the widget, area, and buffer.
+    Registered(Value),
+    /// The widget's own `render` method, with its arity.
+    Method(i64),
+}
+
+/// How to render a widget with a state object.
+pub enum ResolvedStateful {
+    Native(StatefulRenderer),
+    Registered(Value),
+}
+
+fn module_name(module: Value) -> Result<Option<String>, Error> {
+    module.funcall("name", ())
+}
+
+/// An entry for `RESOLVED` or `RESOLVED_STATEFUL`.
+fn entry(ruby: &Ruby, tag: &str, value: Option<Value>) -> RArray {
+    let tag = ruby.to_symbol(tag).as_value();
+    match value {
+        Some(value) => ruby.ary_new_from_values(&[tag, value]),
+        None => ruby.ary_new_from_values(&[tag]),
+    }
+}
+
+/// Reads an entry written by `entry`.
+fn read_entry(entry: Value) -> Result<(Cow<'static, str>, Option<Value>), Error> {
+    let entry = RArray::try_convert(entry)?;
+    let tag: Symbol = entry.entry(0)?;
+    let value = (entry.len() > 1).then(|| entry.entry(1)).transpose()?;
+    Ok((tag.name()?, value))
+}
+
+/// Looks `class` up in `cache`, or finds it with `find` and remembers it.
+///
+/// Singleton classes belong to a single object, so they are not remembered.
+fn cached(
+    cache: RHash,
+    class: RClass,
+    find: impl FnOnce() -> Result<RArray, Error>,
+) -> Result<Value, Error> {
+    if let Some(found) = cache.get(class) {
+        return Ok(found);
+    }
+    let found = find()?;
+    if !class.funcall::<_, _, bool>("singleton_class?", ())? {
+        cache.aset(class, found)?;
+    }
+    Ok(found.as_value())
+}
+
+/// Finds the renderer for `node`, or `None` if nothing knows how to render it.
+pub fn resolve(node: Value) -> Result<Option<Resolved>, Error> {
+    let ruby = Ruby::get().unwrap();
+    let class = node.class();
+    let found = cached(ruby.get_inner(&RESOLVED), class, || {
+        resolve_class(ruby, class)
+    })?;
+    let (tag, value) = read_entry(found)?;
+    match (tag.as_ref(), value) {
+        ("native", Some(index)) => Ok(Some(Resolved::Native(
+            WIDGETS[usize::try_convert(index)?].1,
+        ))),
+        ("registered", Some(renderer)) => Ok(Some(Resolved::Registered(renderer))),
+        ("method", Some(arity)) => Ok(Some(Resolved::Method(i64::try_convert(arity)?))),
+        // A `render` defined on the object itself, or answered by `method_missing`.
+        _ if node.respond_to("render", true)? => {
+            let method: Value = node.funcall("method", (ruby.to_symbol("render"),))?;
+            Ok(Some(Resolved::Method(method.funcall("arity", ())?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Finds how to render instances of `class`, as an entry for `RESOLVED`.
+fn resolve_class(ruby: &Ruby, class: RClass) -> Result<RArray, Error> {
+    let registered = ruby.get_inner(&RENDERERS);
+    let render = ruby.to_symbol("render");
+    let method: Option<Value> = if class.funcall::<_, _, bool>("method_defined?", (render,))?
+        || class.funcall::<_, _, bool>("private_method_defined?", (render,))?
+    {
+        Some(class.funcall("instance_method", (render,))?)
+    } else {
+        None
+    };
+    let owner: Option<Value> = method.map(|m| m.funcall("owner", ())).transpose()?;
+
+    for ancestor in class.funcall::<_, _, RArray>("ancestors", ())? {
+        if let Some(renderer) = registered.get(ancestor) {
+            return Ok(entry(ruby, "registered", Some(renderer)));
+        }
+        if let Some(name) = module_name(ancestor)? {
+            if let Some(index) = WIDGETS.iter().position(|(widget, _)| *widget == name) {
+                return Ok(entry(ruby, "native", Some(ruby.into_value(index))));
+            }
+        }
+        if let (Some(owner), Some(method)) = (owner, method) {
+            if ancestor.equal(owner)? {
+                let arity: i64 = method.funcall("arity", ())?;
+                return Ok(entry(ruby, "method", Some(ruby.into_value(arity))));
+            }
+        }
+    }
+    Ok(entry(ruby, "instance", None))
+}
+
+/// Finds the renderer for `widget` with `state`, or `None` if there is none.
+///
+/// Walks the widget's ancestors, and for each, the state's ancestors.
+pub fn resolve_stateful(widget: Value, state: Value) -> Result<Option<ResolvedStateful>, Error> {
+    let ruby = Ruby::get().unwrap();
+    let widget_class = widget.class();
+    let state_class = state.class();
+    let resolved = ruby.get_inner(&RESOLVED_STATEFUL);
+    let by_state = match resolved.get(widget_class).and_then(RHash::from_value) {
+        Some(by_state) => by_state,
+        None => {
+            let by_state = ruby.hash_new();
+            if !widget_class.funcall::<_, _, bool>("singleton_class?", ())? {
+                resolved.aset(widget_class, by_state)?;
+            }
+            by_state
+        }
+    };
+    let found = cached(by_state, state_class, || {
+        resolve_classes(ruby, widget_class, state_class)
+    })?;
+    let (tag, value) = read_entry(found)?;
+    match (tag.as_ref(), value) {
+        ("native", Some(index)) => Ok(Some(ResolvedStateful::Native(
+            STATEFUL_WIDGETS[usize::try_convert(index)?].2,
+        ))),
+        ("registered", Some(renderer)) => Ok(Some(ResolvedStateful::Registered(renderer))),
+        _ => Ok(None),
+    }
+}
+
+/// Finds how to render instances of `widget_class` with instances of `state_class`,
+/// as an entry for `RESOLVED_STATEFUL`.
+fn resolve_classes(
+    ruby: &Ruby,
+    widget_class: RClass,
+    state_class: RClass,
+) -> Result<RArray, Error> {
+    let registered = ruby.get_inner(&STATEFUL_RENDERERS);
+    let state_ancestors: RArray = state_class.funcall("ancestors", ())?;
+    let mut state_names = Vec::with_capacity(state_ancestors.len());
+    for ancestor in state_ancestors {
+        state_names.push(module_name(ancestor)?);
+    }
+
+    for ancestor in widget_class.funcall::<_, _, RArray>("ancestors", ())? {
+        if let Some(by_state) = registered.get(ancestor).and_then(RHash::from_value) {
+            for state_ancestor in state_ancestors {
+                if let Some(renderer) = by_state.get(state_ancestor) {
+                    return Ok(entry(ruby, "registered", Some(renderer)));
+                }
+            }
+        }
+        let Some(name) = module_name(ancestor)? else {
+            continue;
+        };
+        for state_name in state_names.iter().flatten() {
+            let found = STATEFUL_WIDGETS
+                .iter()
+                .position(|(w, s, _)| *w == name && *s == state_name.as_str());
+            if let Some(index) = found {
+                return Ok(entry(ruby, "native", Some(ruby.into_value(index))));
+            }
+        }
+    }
+    Ok(entry(ruby, "none", None))
+}
+
+/// Registers a renderer for instances of `module` and its descendants.
+pub fn register_widget(module: Value, renderer: Value) -> Result<(), Error> {
+    let ruby = Ruby::get().unwrap();
+    ruby.get_inner(&RENDERERS).aset(module, renderer)?;
+    forget_resolved(ruby)
+}
+
+/// Registers a renderer for instances of `module` drawn with instances of `state_module`.
+pub fn register_stateful_widget(
+    module: Value,
+    state_module: Value,
+    renderer: Value,
+) -> Result<(), Error> {
+    let ruby = Ruby::get().unwrap();
+    let registered = ruby.get_inner(&STATEFUL_RENDERERS);
+    let by_state = match registered.get(module).and_then(RHash::from_value) {
+        Some(by_state) => by_state,
+        None => {
+            let by_state = ruby.hash_new();
+            registered.aset(module, by_state)?;
+            by_state
+        }
+    };
+    by_state.aset(state_module, renderer)?;
+    forget_resolved(ruby)
+}
+
+/// Clears what earlier lookups found, which a new renderer may change.
+fn forget_resolved(ruby: &Ruby) -> Result<(), Error> {
+    ruby.get_inner(&RESOLVED).clear()?;
+    ruby.get_inner(&RESOLVED_STATEFUL).clear()
+}
+
+/// Describes a node nothing can render, for the error raised about it.
+pub fn unknown_widget_message(node: Value) -> String {
+    // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
+    let class_name = unsafe { node.class().name() }.into_owned();
+    format!(
+        "Don't know how to render {class_name}: define render(area) or render(area, buffer) \
+         on it, or register a renderer with RatatuiRuby.register_widget"
+    )
+}
+
+/// Registers the registry functions with Ruby.
+pub fn register(module: RModule) -> Result<(), Error> {
+    module.define_module_function("_register_widget", function!(register_widget, 2))?;
+    module.define_module_function(
+        "_register_stateful_widget",
+        function!(register_stateful_widget, 3),
+    )?;
+    Ok(())
+}
@@ -1,74 +1,95 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::style::{parse_color_value, parse_modifier_str, parse_style};
-use crate::widgets;
-use magnus::{prelude::*, Error, RArray, Value};
-use ratatui::{buffer::Buffer, layout::Rect, style::Style, Frame};
+use crate::registry::{self, Resolved};
+use crate::style::{
+    parse_border_set, parse_border_type, parse_color_value, parse_modifier_str, parse_style,
+};
+use bumpalo::Bump;
+use magnus::{prelude::*, Error, RArray, Symbol, Value};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Widget},
+    Frame,
+};
 
 pub fn render_node(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
-    if node.respond_to("render", true)? {
-        let ruby = magnus::Ruby::get().unwrap();
-        let ruby_area = {
-            let module = ruby.define_module("RatatuiRuby")?;
-            let layout_mod = module.const_get::<_, magnus::RModule>("Layout")?;
-            let class = layout_mod.const_get::<_, magnus::RClass>("Rect")?;
-            class.funcall::<_, _, Value>("new", (area.x, area.y, area.width, area.height))?
-        };
-
-        // Call render with just the area (no buffer!)
-        let commands: Value = node.funcall("render", (ruby_area,))?;
-
-        // Process returned draw commands
-        if let Some(arr) = RArray::from_value(commands) {
-            for i in 0..arr.len() {
-                let ruby = magnus::Ruby::get().unwrap();
-                let index = isize::try_from(i)
-                    .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
-                let cmd: Value = arr.entry(index)?;
-                process_draw_command(frame.buffer_mut(), cmd)?;
-            }
+    let ruby = magnus::Ruby::get().unwrap();
+    match registry::resolve(node)? {
+        Some(Resolved::Native(render)) => render(frame, area, node),
+        Some(Resolved::Registered(renderer)) => {
+            let ruby_area = rect_value(area)?;
+            crate::buffer::lend(frame, |buffer| {
+                renderer.funcall::<_, _, Value>("call", (node, ruby_area, buffer))
+            })?;
+            Ok(())
         }
-        return Ok(());
+        Some(Resolved::Method(arity)) => render_with_method(frame, area, node, arity),
+        None => Err(Error::new(
+            ruby.exception_type_error(),
+            registry::unknown_widget_message(node),
+        )),
     }
+}
 
-    // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
-    let class_name = unsafe { node.class().name() }.into_owned();
+/// Renders a custom widget through its own `render` method.
+fn render_with_method(frame: &mut Frame, area: Rect, node: Value, arity: i64) -> Result<(), Error> {
+    let ruby_area = rect_value(area)?;
 
-    match class_name.as_str() {
-        "RatatuiRuby::Widgets::Paragraph" => widgets::paragraph::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Clear" => widgets::clear::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Cursor" => widgets::cursor::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Overlay" => widgets::overlay::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Center" => widgets::center::render(frame, area, node)?,
-        "RatatuiRuby::Layout::Layout" => widgets::layout::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::List" => widgets::list::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Gauge" => widgets::gauge::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::LineGauge" => widgets::line_gauge::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Table" => widgets::table::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Block" => widgets::block::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Tabs" => widgets::tabs::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Scrollbar" => widgets::scrollbar::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::BarChart" => widgets::barchart::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Canvas" => widgets::canvas::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Calendar" => widgets::calendar::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Sparkline" => widgets::sparkline::render(frame, area, node)?,
-        "RatatuiRuby::Widgets::Chart" | "RatatuiRuby::LineChart" => {
-            widgets::chart::render(frame, area, node)?;
-        }
-        "RatatuiRuby::Widgets::RatatuiLogo" => widgets::ratatui_logo::render(frame, area, node),
-        "RatatuiRuby::Widgets::RatatuiMascot" => {
-            widgets::ratatui_mascot::render_ratatui_mascot(frame, area, node)?;
+    // Widgets whose render requires (area, buffer) write to the frame's buffer directly.
+    // Methods taking `*args`, like those of delegators, may only want the area, so they
+    // get the area alone.
+    if arity == 2 || arity <= -3 {
+        return crate::buffer::lend(frame, |buffer| {
+            node.funcall::<_, _, Value>("render", (ruby_area, buffer))
+        })
+        .map(|_| ());
+    }
+
+    // Call render with just the area (no buffer!)
+    let commands: Value = node.funcall("render", (ruby_area,))?;
+
+    // Process returned draw commands
+    if let Some(arr) = RArray::from_value(commands) {
+        for i in 0..arr.len() {
+            let ruby = magnus::Ruby::get().unwrap();
+            let index = isize::try_from(i)
+                .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
+            let cmd: Value = arr.entry(index)?;
+            process_draw_command(frame, area, cmd)?;
         }
-        _ => {}
     }
     Ok(())
 }
 
-fn process_draw_command(buffer: &mut Buffer, cmd: Value) -> Result<(), Error> {
+/// Returns `area` as a Ruby `Layout::Rect`.
+pub fn rect_value(area: Rect) -> Result<Value, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let module = ruby.define_module("RatatuiRuby")?;
+    let layout_mod = module.const_get::<_, magnus::RModule>("Layout")?;
+    let class = layout_mod.const_get::<_, magnus::RClass>("Rect")?;
+    class.funcall("new", (area.x, area.y, area.width, area.height))
+}
+
+/// Reads a `Layout::Rect` (or anything with `x`, `y`, `width`, and `height`).
+pub fn parse_rect(area_val: Value) -> Result<Rect, Error> {
+    let x: u16 = area_val.funcall("x", ())?;
+    let y: u16 = area_val.funcall("y", ())?;
+    let width: u16 = area_val.funcall("width", ())?;
+    let height: u16 = area_val.funcall("height", ())?;
+    Ok(Rect::new(x, y, width, height))
+}
+
+/// Runs one draw command returned by a custom widget rendered into `area`.
+///
+/// Line and region commands are clipped to `area`.
+fn process_draw_command(frame: &mut Frame, area: Rect, cmd: Value) -> Result<(), Error> {
     let ruby = magnus::Ruby::get().unwrap();
     // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
     let class_name = unsafe { cmd.class().name() }.into_owned();
+    let buffer = frame.buffer_mut();
 
     match class_name.as_str() {
         "RatatuiRuby::Draw::StringCmd" => {
@@ -89,37 +110,77 @@ fn process_draw_command(buffer: &mut Buffer, cmd: Value) -> Result<(), Error> {
                 return Ok(());
             }
 
-            let symbol: String = cell_val.funcall("char", ())?;
-            let fg_val: Value = cell_val.funcall("fg", ())?;
-            let bg_val: Value = cell_val.funcall("bg", ())?;
-            let modifiers_val: Value = cell_val.funcall("modifiers", ())?;
-
-            let mut style = Style::default();
+            let (symbol, style) = parse_cell(cell_val)?;
 
-            if !fg_val.is_nil() {
-                if let Some(color) = parse_color_value(fg_val)? {
-                    style = style.fg(color);
-                }
+            if let Some(cell) = buffer.cell_mut((x, y)) {
+                cell.set_symbol(&symbol).set_style(style);
             }
-            if !bg_val.is_nil() {
-                if let Some(color) = parse_color_value(bg_val)? {
-                    style = style.bg(color);
-                }
+        }
+        "RatatuiRuby::Draw::WidgetCmd" => {
+            let widget: Value = cmd.funcall("widget", ())?;
+            let target = parse_rect(cmd.funcall("area", ())?)?.intersection(buffer.area);
+            render_node(frame, target, widget)?;
+        }
+        "RatatuiRuby::Draw::LineCmd" => {
+            let x: u16 = cmd.funcall("x", ())?;
+            let y: u16 = cmd.funcall("y", ())?;
+            let line_val: Value = cmd.funcall("line", ())?;
+            let max_width: Option<u16> = cmd.funcall("max_width", ())?;
+            let line = match String::try_convert(line_val) {
+                Ok(text) => Line::raw(text),
+                Err(_) => crate::text::parse_line(line_val)?,
+            };
+            let clip = area.intersection(buffer.area);
+            if x < clip.left() || x >= clip.right() || y < clip.top() || y >= clip.bottom() {
+                return Ok(());
             }
-
-            if let Some(mods_array) = RArray::from_value(modifiers_val) {
-                for i in 0..mods_array.len() {
-                    let index = isize::try_from(i)
-                        .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
-                    let mod_str: String = mods_array.entry::<String>(index)?;
-                    if let Some(modifier) = parse_modifier_str(&mod_str) {
-                        style = style.add_modifier(modifier);
-                    }
+            let room = clip.right() - x;
+            buffer.set_line(x, y, &line, max_width.map_or(room, |max| max.min(room)));
+        }
+        "RatatuiRuby::Draw::FillCmd" => {
+            let target = parse_rect(cmd.funcall("area", ())?)?.intersection(area);
+            let symbol: String = cmd.funcall("symbol", ())?;
+            let style = parse_style(cmd.funcall("style", ())?)?;
+            for position in target.intersection(buffer.area).positions() {
+                buffer[position].reset();
+                buffer[position].set_symbol(&symbol).set_style(style);
+            }
+        }
+        "RatatuiRuby::Draw::StyleCmd" => {
+            let target = parse_rect(cmd.funcall("area", ())?)?.intersection(area);
+            let style = parse_style(cmd.funcall("style", ())?)?;
+            buffer.set_style(target.intersection(buffer.area), style);
+        }
+        "RatatuiRuby::Draw::BoxCmd" => {
+            let target = parse_rect(cmd.funcall("area", ())?)?;
+            let border_type: Symbol = cmd.funcall("border_type", ())?;
+            let border_set: Value = cmd.funcall("border_set", ())?;
+            let style = parse_style(cmd.funcall("style", ())?)?;
+            let bump = Bump::new();
+            let mut block = Block::bordered().border_style(style);
+            block = if border_set.is_nil() {
+                block.border_type(parse_border_type(&border_type.to_string()))
+            } else {
+                block.border_set(parse_border_set(border_set, &bump)?)
+            };
+            // Drawn whole into a scratch buffer, so clipping keeps the corners in place.
+            let mut scratch = ratatui::buffer::Buffer::empty(target);
+            block.render(target, &mut scratch);
+            let inner = Block::bordered().inner(target);
+            for position in target
+                .intersection(area)
+                .intersection(buffer.area)
+                .positions()
+            {
+                if !inner.contains(position) {
+                    buffer[position] = scratch[position].clone();
                 }
             }
-
-            if let Some(cell) = buffer.cell_mut((x, y)) {
-                cell.set_symbol(&symbol).set_style(style);
+        }
+        "RatatuiRuby::Draw::ClearCmd" => {
+            let target = parse_rect(cmd.funcall("area", ())?)?.intersection(area);
+            for position in target.intersection(buffer.area).positions() {
+                buffer[position].reset();
             }
         }
         _ => {
@@ -132,3 +193,48 @@ fn process_draw_command(buffer: &mut Buffer, cmd: Value) -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Reads a `Buffer::Cell` as the symbol and style to give a buffer cell.
+pub fn parse_cell(cell_val: Value) -> Result<(String, Style), Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let symbol: String = cell_val.funcall("char", ())?;
+    let fg_val: Value = cell_val.funcall("fg", ())?;
+    let bg_val: Value = cell_val.funcall("bg", ())?;
+    let modifiers_val: Value = cell_val.funcall("modifiers", ())?;
+    // Only `Buffer::Cell` has a hyperlink.
+    let hyperlink: Option<String> = if cell_val.respond_to("hyperlink", false)? {
+        cell_val.funcall("hyperlink", ())?
+    } else {
+        None
+    };
+
+    let mut style = Style::default();
+
+    if !fg_val.is_nil() {
+        if let Some(color) = parse_color_value(fg_val)? {
+            style = style.fg(color);
+        }
+    }
+    if !bg_val.is_nil() {
+        if let Some(color) = parse_color_value(bg_val)? {
+            style = style.bg(color);
+        }
+    }
+
+    if let Some(mods_array) = RArray::from_value(modifiers_val) {
+        for i in 0..mods_array.len() {
+            let index = isize::try_from(i)
+                .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
+            let mod_str: String = mods_array.entry::<String>(index)?;
+            if let Some(modifier) = parse_modifier_str(&mod_str) {
+                style = style.add_modifier(modifier);
+            }
+        }
+    }
+
+    if let Some(url) = hyperlink {
+        style = style.patch(crate::hyperlink::style_for(&url));
+    }
+
+    Ok((symbol, style))
+}
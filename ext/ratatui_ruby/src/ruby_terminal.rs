@@ -0,0 +1,267 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! `RatatuiRuby::Terminal`, a terminal as an object.
+//!
+//! The module-level functions drive one terminal per process, stored in `TERMINAL` and
+//! `EVENT_QUEUE`. `Terminal.default` stands for that terminal and forwards to them.
+//! Every other `Terminal` is headless: it owns a test backend, an event queue, a color
+//! depth, and its draw statistics, and never touches the real terminal.
+//!
+//! There is no second real terminal. Raw mode, the input reader, and the crash handlers
+//! that restore the screen are process-wide, so they stay with `TERMINAL`.
+//!
+//! Headless terminals are borrowed for the whole `draw`, so the draw block cannot
+//! inspect or resize the terminal it is drawing on. Doing so raises instead of
+//! deadlocking.
+
+use magnus::{function, method, prelude::*, Error, RHash, Ruby, Value};
+use ratatui::{backend::TestBackend, crossterm::event::Event, Terminal, TerminalOptions};
+use std::cell::{RefCell, RefMut};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::color_depth::{self, ColorDepth};
+use crate::draw_stats::{self, CountingBackend, DrawStats};
+use crate::terminal::terminal_error;
+
+struct Headless {
+    terminal: RefCell<Terminal<CountingBackend<TestBackend>>>,
+    events: RefCell<VecDeque<Event>>,
+    color_depth: ColorDepth,
+    last_draw_stats: RefCell<Option<DrawStats>>,
+    screen: usize,
+}
+
+impl Headless {
+    fn terminal(&self) -> Result<RefMut<'_, Terminal<CountingBackend<TestBackend>>>, Error> {
+        self.terminal
+            .try_borrow_mut()
+            .map_err(|_| terminal_error("Terminal is busy drawing".to_string()))
+    }
+}
+
+impl Drop for Headless {
+    fn drop(&mut self) {
+        crate::hyperlink::forget_screen(self.screen);
+    }
+}
+
+#[magnus::wrap(class = "RatatuiRuby::Terminal")]
+pub struct RubyTerminal {
+    /// `None` for the module-level terminal.
+    headless: Option<Headless>,
+}
+
+impl RubyTerminal {
+    /// Creates a headless terminal of the given size.
+    pub fn new(
+        width: u16,
+        height: u16,
+        viewport: Value,
+        color_depth: Value,
+    ) -> Result<Self, Error> {
+        let viewport = crate::terminal::parse_viewport(viewport)?;
+        let color_depth = color_depth::parse(color_depth)?.unwrap_or(ColorDepth::TrueColor);
+        let terminal = Terminal::with_options(
+            CountingBackend::new(TestBackend::new(width, height)),
+            TerminalOptions { viewport },
+        )
+        .map_err(|e| terminal_error(e.to_string()))?;
+        Ok(Self {
+            headless: Some(Headless {
+                terminal: RefCell::new(terminal),
+                events: RefCell::new(VecDeque::new()),
+                color_depth,
+                last_draw_stats: RefCell::new(None),
+                screen: crate::hyperlink::new_screen(),
+            }),
+        })
+    }
+
+    /// Returns the object standing for the module-level terminal.
+    pub fn module_level() -> Self {
+        Self { headless: None }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.headless.is_none()
+    }
+
+    /// Draws a frame, taking the same arguments as `RatatuiRuby.draw`.
+    pub fn draw(&self, args: &[Value]) -> Result<Option<RHash>, Error> {
+        let Some(headless) = &self.headless else {
+            return crate::draw(args);
+        };
+        let ruby = Ruby::get().unwrap();
+        let tree = crate::draw_args(&ruby, args)?;
+        let mut terminal = headless.terminal()?;
+        // Forget the cells of an earlier frame that failed.
+        terminal.backend_mut().take_counts();
+        let mut render_error: Option<Error> = None;
+        let mut build_duration = Duration::ZERO;
+        let mut render_duration = Duration::ZERO;
+        let mut cursor_request = crate::cursor::Request::default();
+        let started = Instant::now();
+
+        let links = crate::hyperlink::FrameLinks::start(headless.screen);
+        let count = terminal
+            .draw(|f| {
+                let started = Instant::now();
+                match crate::teardown::catch_panic(|| {
+                    crate::render_frame(&ruby, f, tree, headless.color_depth)
+                }) {
+                    Ok(rendered) => {
+                        render_duration += rendered.render_duration;
+                        cursor_request = rendered.cursor;
+                    }
+                    Err(e) => render_error = Some(e),
+                }
+                build_duration += started.elapsed();
+            })
+            .map(|completed| {
+                links.finish(completed.buffer);
+                completed.count
+            })
+            .map_err(|e| terminal_error(e.to_string()))?;
+        crate::cursor::apply_offscreen(&mut terminal, cursor_request);
+        let duration = started.elapsed();
+
+        if let Some(e) = render_error {
+            return Err(e);
+        }
+
+        let (_, cells_changed) = terminal.backend_mut().take_counts();
+        let stats = DrawStats {
+            frame_count: count.wrapping_add(1),
+            duration,
+            convert_duration: build_duration.saturating_sub(render_duration),
+            render_duration,
+            output_duration: duration.saturating_sub(build_duration),
+            cells_changed,
+            bytes_written: 0,
+        };
+        *headless.last_draw_stats.borrow_mut() = Some(stats);
+        draw_stats::to_hash(&stats).map(Some)
+    }
+
+    pub fn last_draw_stats(&self) -> Result<Option<RHash>, Error> {
+        match &self.headless {
+            Some(headless) => {
+                let last = *headless.last_draw_stats.borrow();
+                last.as_ref().map(draw_stats::to_hash).transpose()
+            }
+            None => draw_stats::last_draw_stats(),
+        }
+    }
+
+    /// Returns the next event. Headless terminals only have injected events, so they
+    /// return `nil` at once when none are queued, whatever the timeout.
+    pub fn poll_event(ruby: &Ruby, rb_self: &Self, timeout: Option<f64>) -> Result<Value, Error> {
+        let Some(headless) = &rb_self.headless else {
+            return crate::events::poll_event(ruby, timeout);
+        };
+        let event = headless.events.borrow_mut().pop_front();
+        match event {
+            Some(event) => crate::events::handle_event(event),
+            None => Ok(ruby.qnil().as_value()),
+        }
+    }
+
+    /// Queues an event, taking the same arguments as `RatatuiRuby.inject_test_event`.
+    pub fn inject_event(&self, event_type: String, data: RHash) -> Result<(), Error> {
+        let Some(headless) = &self.headless else {
+            return crate::events::inject_test_event(event_type, data);
+        };
+        let event = crate::events::parse_test_event(&event_type, data)?;
+        headless.events.borrow_mut().push_back(event);
+        Ok(())
+    }
+
+    pub fn clear_events(&self) {
+        match &self.headless {
+            Some(headless) => headless.events.borrow_mut().clear(),
+            None => crate::events::clear_events(),
+        }
+    }
+
+    pub fn buffer_content(&self) -> Result<String, Error> {
+        let Some(headless) = &self.headless else {
+            return crate::terminal::get_buffer_content();
+        };
+        let terminal = headless.terminal()?;
+        Ok(crate::terminal::buffer_content(terminal.backend().buffer()))
+    }
+
+    /// Returns a copy of the screen.
+    pub fn buffer(&self) -> Result<crate::buffer::RubyBuffer, Error> {
+        let Some(headless) = &self.headless else {
+            return crate::terminal::get_buffer();
+        };
+        let terminal = headless.terminal()?;
+        Ok(crate::buffer::RubyBuffer::copy_of(
+            terminal.backend().buffer(),
+        ))
+    }
+
+    pub fn cell_at(&self, x: u16, y: u16) -> Result<RHash, Error> {
+        let Some(headless) = &self.headless else {
+            return crate::terminal::get_cell_at(x, y);
+        };
+        let terminal = headless.terminal()?;
+        crate::terminal::cell_at(
+            terminal.backend().buffer(),
+            x,
+            y,
+            &crate::hyperlink::LinkTargets::Shared,
+        )
+    }
+
+    pub fn cursor_position(&self) -> Result<Option<(u16, u16)>, Error> {
+        let Some(headless) = &self.headless else {
+            return crate::terminal::get_cursor_position();
+        };
+        let mut terminal = headless.terminal()?;
+        let position = terminal
+            .get_cursor_position()
+            .map_err(|e| terminal_error(e.to_string()))?;
+        Ok(Some(position.into()))
+    }
+
+    /// Resizes the screen, as a terminal window resize would.
+    pub fn resize(&self, width: u16, height: u16) -> Result<(), Error> {
+        let Some(headless) = &self.headless else {
+            return crate::terminal::resize_terminal(width, height);
+        };
+        let mut terminal = headless.terminal()?;
+        terminal.backend_mut().resize(width, height);
+        terminal
+            .resize(ratatui::layout::Rect::new(0, 0, width, height))
+            .map_err(|e| terminal_error(e.to_string()))
+    }
+}
+
+/// Registers the `Terminal` class with Ruby.
+pub fn register(ruby: &Ruby, module: magnus::RModule) -> Result<(), Error> {
+    let class = module.define_class("Terminal", ruby.class_object())?;
+    class.define_singleton_method("_new", function!(RubyTerminal::new, 4))?;
+    class.define_singleton_method("_default", function!(RubyTerminal::module_level, 0))?;
+    class.define_method("default?", method!(RubyTerminal::is_default, 0))?;
+    class.define_method("_draw", method!(RubyTerminal::draw, -1))?;
+    class.define_method(
+        "_last_draw_stats",
+        method!(RubyTerminal::last_draw_stats, 0),
+    )?;
+    class.define_method("_poll_event", method!(RubyTerminal::poll_event, 1))?;
+    class.define_method("_inject_event", method!(RubyTerminal::inject_event, 2))?;
+    class.define_method("clear_events", method!(RubyTerminal::clear_events, 0))?;
+    class.define_method("_buffer_content", method!(RubyTerminal::buffer_content, 0))?;
+    class.define_method("buffer", method!(RubyTerminal::buffer, 0))?;
+    class.define_method("_cell_at", method!(RubyTerminal::cell_at, 2))?;
+    class.define_method(
+        "_cursor_position",
+        method!(RubyTerminal::cursor_position, 0),
+    )?;
+    class.define_method("resize", method!(RubyTerminal::resize, 2))?;
+    Ok(())
+}
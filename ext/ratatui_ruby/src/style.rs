@@ -394,20 +394,25 @@ fn parse_borders<'a>(
             block = block.border_set(parse_border_set(v, bump)?);
         } else if let Ok(v) = block_val.funcall::<&str, _, Value>("border_type", ()) {
             if let Some(sym) = Symbol::from_value(v) {
-                match sym.to_string().as_str() {
-                    "rounded" => block = block.border_type(BorderType::Rounded),
-                    "double" => block = block.border_type(BorderType::Double),
-                    "thick" => block = block.border_type(BorderType::Thick),
-                    "quadrant_inside" => block = block.border_type(BorderType::QuadrantInside),
-                    "quadrant_outside" => block = block.border_type(BorderType::QuadrantOutside),
-                    _ => block = block.border_type(BorderType::Plain),
-                }
+                block = block.border_type(parse_border_type(&sym.to_string()));
             }
         }
     }
     Ok(block)
 }
 
+/// Maps a border type name to a `BorderType`, falling back to plain lines.
+pub fn parse_border_type(name: &str) -> BorderType {
+    match name {
+        "rounded" => BorderType::Rounded,
+        "double" => BorderType::Double,
+        "thick" => BorderType::Thick,
+        "quadrant_inside" => BorderType::QuadrantInside,
+        "quadrant_outside" => BorderType::QuadrantOutside,
+        _ => BorderType::Plain,
+    }
+}
+
 fn parse_padding(block_val: Value, block: Block<'_>) -> Block<'_> {
     if let Ok(padding_val) = block_val.funcall::<&str, _, Value>("padding", ()) {
         if padding_val.is_nil() {
@@ -0,0 +1,357 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Last-resort terminal restoration.
+//!
+//! `restore_terminal` needs the terminal lock, which a panicking thread may hold, and
+//! crossterm's raw-mode state, which is not safe to touch from a signal handler. This
+//! module tracks what TUI mode changed in atomics, so a panic hook or a crash signal
+//! handler can undo it without either.
+//!
+//! Panics inside draw callbacks are caught and raised in Ruby as
+//! `RatatuiRuby::Error::Panic`. The terminal stays in TUI mode, and the usual `ensure`
+//! in `RatatuiRuby.run` restores it.
+
+use magnus::{prelude::*, Error};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::io::Write;
+use std::panic::{AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once, PoisonError};
+
+use crate::output::Output;
+use crate::terminal::TerminalConfig;
+
+/// Whether the real terminal is in TUI mode (raw mode, mouse capture, ...).
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
+/// Whether frames are drawn inside synchronized updates, which a crash may leave open.
+static SYNCHRONIZED_OUTPUT: AtomicBool = AtomicBool::new(false);
+/// Whether the cursor shape differs from the user's own.
+static CURSOR_STYLED: AtomicBool = AtomicBool::new(false);
+/// Whether the user's window title is saved on the title stack.
+static TITLE_PUSHED: AtomicBool = AtomicBool::new(false);
+/// Where the terminal draws. Kept until the next `entered`, so the descriptor the signal
+/// handler writes to stays open.
+static OUTPUT: Mutex<Option<Output>> = Mutex::new(None);
+
+const END_SYNCHRONIZED_UPDATE: &[u8] = b"\x1b[?2026l";
+const POP_KEYBOARD_FLAGS: &[u8] = b"\x1b[<1u";
+/// Disables mouse capture, focus events, and bracketed paste. Disabling a mode that is
+/// already off is harmless, so this does not track which ones the app toggled.
+const DISABLE_INPUT_MODES: &[u8] =
+    b"\x1b[?1006l\x1b[?1015l\x1b[?1003l\x1b[?1002l\x1b[?1000l\x1b[?1004l\x1b[?2004l";
+const LEAVE_ALTERNATE_SCREEN: &[u8] = b"\x1b[?1049l";
+const NEW_LINE: &[u8] = b"\r\n";
+const RESET_CURSOR_STYLE: &[u8] = b"\x1b[0 q";
+const POP_TITLE: &[u8] = b"\x1b[23;0t";
+const SHOW_CURSOR: &[u8] = b"\x1b[?25h";
+
+thread_local! {
+    /// Nesting depth of `catch_panic` on this thread.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Message of the last panic caught by `catch_panic`.
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs the panic hook and crash signal handlers. Safe to call more than once.
+pub fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) > 0 {
+                // `catch_panic` reports it as a Ruby exception instead.
+                CAUGHT.with(|caught| *caught.borrow_mut() = Some(panic_message(info)));
+                return;
+            }
+            restore_now();
+            previous(info);
+        }));
+        signals::install();
+    });
+}
+
+/// Records that `init_terminal` or `resume_terminal` put the terminal into TUI mode.
+pub fn entered(config: &TerminalConfig) {
+    ALTERNATE_SCREEN.store(config.uses_alternate_screen(), Ordering::SeqCst);
+    KEYBOARD_ENHANCED.store(!config.keyboard_enhancement.is_empty(), Ordering::SeqCst);
+    SYNCHRONIZED_OUTPUT.store(config.synchronized_output, Ordering::SeqCst);
+    let mut output = OUTPUT.lock().unwrap_or_else(PoisonError::into_inner);
+    #[cfg(unix)]
+    signals::set_output_fd(config.output.raw_fd());
+    *output = Some(config.output.clone());
+    drop(output);
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Records whether a frame changed the cursor shape.
+pub fn set_cursor_styled(styled: bool) {
+    CURSOR_STYLED.store(styled, Ordering::SeqCst);
+}
+
+/// Records whether `set_title` saved the user's title, so it can be put back.
+pub fn set_title_pushed(pushed: bool) {
+    TITLE_PUSHED.store(pushed, Ordering::SeqCst);
+}
+
+/// Claims the job of leaving TUI mode.
+///
+/// Returns `false` if the terminal already left it, so teardown runs exactly once.
+pub fn leave() -> bool {
+    ACTIVE.swap(false, Ordering::SeqCst)
+}
+
+/// Whether the terminal is in TUI mode.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Leaves TUI mode without the terminal lock. Used when the process is going down.
+fn restore_now() {
+    if !leave() {
+        return;
+    }
+    let _ = ratatui::crossterm::terminal::disable_raw_mode();
+    let mut out = OUTPUT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or(Output::Stdout);
+    let _ = out.write_all(&teardown_sequence());
+    let _ = out.flush();
+}
+
+/// The escape sequences `restore_terminal` would send, based on the recorded mode.
+fn teardown_sequence() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    if SYNCHRONIZED_OUTPUT.load(Ordering::SeqCst) {
+        bytes.extend_from_slice(END_SYNCHRONIZED_UPDATE);
+    }
+    if KEYBOARD_ENHANCED.load(Ordering::SeqCst) {
+        bytes.extend_from_slice(POP_KEYBOARD_FLAGS);
+    }
+    bytes.extend_from_slice(DISABLE_INPUT_MODES);
+    if ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+        bytes.extend_from_slice(LEAVE_ALTERNATE_SCREEN);
+    } else {
+        bytes.extend_from_slice(NEW_LINE);
+    }
+    if CURSOR_STYLED.load(Ordering::SeqCst) {
+        bytes.extend_from_slice(RESET_CURSOR_STYLE);
+    }
+    if TITLE_PUSHED.load(Ordering::SeqCst) {
+        bytes.extend_from_slice(POP_TITLE);
+    }
+    bytes.extend_from_slice(SHOW_CURSOR);
+    bytes
+}
+
+/// Runs `f`, raising any Rust panic inside it as `RatatuiRuby::Error::Panic`.
+///
+/// Panics in native methods called from Ruby (such as `Frame#render_widget`) reach us
+/// as Ruby `fatal` errors, which cannot be rescued. Those are converted as well.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    CATCHING.with(|depth| depth.set(depth.get() + 1));
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|depth| depth.set(depth.get() - 1));
+
+    let ruby = magnus::Ruby::get().unwrap();
+    let caught = CAUGHT.with(|caught| caught.borrow_mut().take());
+    let message = match result {
+        // Magnus already turned the panic into a `fatal` jump; the message is the same.
+        Ok(Err(_)) if caught.is_some() => caught,
+        Ok(result) => return result,
+        Err(payload) => caught.or_else(|| Some(payload_message(payload.as_ref()))),
+    };
+
+    let module = ruby.define_module("RatatuiRuby")?;
+    let error_base = module.const_get::<_, magnus::RClass>("Error")?;
+    let error_class = error_base.const_get("Panic")?;
+    Err(Error::new(
+        error_class,
+        message.unwrap_or_else(|| "panic".to_string()),
+    ))
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let message = payload_message(info.payload());
+    match info.location() {
+        Some(location) => format!("{message} at {}:{}", location.file(), location.line()),
+        None => message,
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic".to_string()
+    }
+}
+
+#[cfg(unix)]
+mod signals {
+    //! Handlers for signals that kill the process without running Ruby `ensure` blocks.
+    //!
+    //! Ruby turns `SIGTERM`, `SIGHUP`, and `SIGINT` into exceptions, so `at_exit` and
+    //! `ensure` restore the terminal for those. Crashes bypass Ruby, so these handlers
+    //! restore the terminal before the process dies.
+    //!
+    //! The previous handler runs first, with the original `siginfo` and context. Ruby's
+    //! `SIGSEGV` handler turns a native stack overflow into a `SystemStackError` and never
+    //! returns, and the app carries on in TUI mode. Only when the previous handler returns
+    //! (or there is none) is the terminal restored and the default action taken.
+
+    use std::ffi::c_void;
+    use std::os::fd::{IntoRawFd, RawFd};
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::OnceLock;
+
+    type Handler = extern "C" fn(libc::c_int);
+    type InfoHandler = extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut c_void);
+
+    const SIGNALS: [libc::c_int; 5] = [
+        libc::SIGSEGV,
+        libc::SIGBUS,
+        libc::SIGILL,
+        libc::SIGFPE,
+        libc::SIGABRT,
+    ];
+
+    static PREVIOUS: [OnceLock<libc::sigaction>; 5] = [const { OnceLock::new() }; 5];
+
+    /// The terminal mode before raw mode, and the fd it belongs to.
+    static ORIGINAL_MODE: OnceLock<Option<(RawFd, libc::termios)>> = OnceLock::new();
+
+    /// The descriptor the terminal draws on.
+    static OUTPUT_FD: AtomicI32 = AtomicI32::new(libc::STDOUT_FILENO);
+
+    pub fn set_output_fd(fd: RawFd) {
+        OUTPUT_FD.store(fd, Ordering::SeqCst);
+    }
+
+    pub fn install() {
+        for (signal, previous) in SIGNALS.iter().zip(&PREVIOUS) {
+            // SAFETY: both structs are plain C data and fully initialized by `zeroed` and
+            // the fields set below. `on_signal` only performs async-signal-safe calls.
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_signal as InfoHandler as usize;
+                action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+                libc::sigemptyset(&raw mut action.sa_mask);
+                let mut old: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(*signal, &raw const action, &raw mut old) == 0 {
+                    let _ = previous.set(old);
+                }
+            }
+        }
+        let _ = ORIGINAL_MODE.get_or_init(original_mode);
+    }
+
+    /// Reads the terminal mode crossterm will change when it enables raw mode.
+    fn original_mode() -> Option<(RawFd, libc::termios)> {
+        // SAFETY: `isatty` only inspects the descriptor.
+        let fd = if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+            libc::STDIN_FILENO
+        } else {
+            // Kept open for the life of the process so the handler can use it.
+            std::fs::File::open("/dev/tty").ok()?.into_raw_fd()
+        };
+        // SAFETY: `termios` is plain C data, and `tcgetattr` fills it in on success.
+        unsafe {
+            let mut mode: libc::termios = std::mem::zeroed();
+            (libc::tcgetattr(fd, &raw mut mode) == 0).then_some((fd, mode))
+        }
+    }
+
+    extern "C" fn on_signal(signal: libc::c_int, info: *mut libc::siginfo_t, context: *mut c_void) {
+        let index = SIGNALS.iter().position(|s| *s == signal);
+        if let Some(previous) = index.and_then(|i| PREVIOUS[i].get()) {
+            call_previous(previous, signal, info, context);
+        }
+
+        // The previous handler returned, so nothing recovered and the process is going down.
+        if super::leave() {
+            if let Some(Some((fd, mode))) = ORIGINAL_MODE.get() {
+                // SAFETY: `tcsetattr` is async-signal-safe, and `mode` is a valid termios.
+                unsafe { libc::tcsetattr(*fd, libc::TCSANOW, mode) };
+            }
+            if super::SYNCHRONIZED_OUTPUT.load(Ordering::SeqCst) {
+                write_all(super::END_SYNCHRONIZED_UPDATE);
+            }
+            if super::KEYBOARD_ENHANCED.load(Ordering::SeqCst) {
+                write_all(super::POP_KEYBOARD_FLAGS);
+            }
+            write_all(super::DISABLE_INPUT_MODES);
+            if super::ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+                write_all(super::LEAVE_ALTERNATE_SCREEN);
+            } else {
+                write_all(super::NEW_LINE);
+            }
+            if super::CURSOR_STYLED.load(Ordering::SeqCst) {
+                write_all(super::RESET_CURSOR_STYLE);
+            }
+            if super::TITLE_PUSHED.load(Ordering::SeqCst) {
+                write_all(super::POP_TITLE);
+            }
+            write_all(super::SHOW_CURSOR);
+        }
+
+        // SAFETY: `signal` and `raise` are async-signal-safe. The raised signal is blocked
+        // until this handler returns, then takes its default action. A fault would also
+        // happen again when the faulting instruction is retried.
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
+    }
+
+    /// Calls the handler that was installed before ours, as the kernel would have.
+    fn call_previous(
+        previous: &libc::sigaction,
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut c_void,
+    ) {
+        let handler = previous.sa_sigaction;
+        if handler == libc::SIG_DFL || handler == libc::SIG_IGN {
+            return;
+        }
+        // SAFETY: `sigaction` reported a function of the kind its flags describe, and
+        // `info` and `context` are the ones the kernel passed to us.
+        unsafe {
+            if previous.sa_flags & libc::SA_SIGINFO == 0 {
+                std::mem::transmute::<libc::sighandler_t, Handler>(handler)(signal);
+            } else {
+                std::mem::transmute::<libc::sighandler_t, InfoHandler>(handler)(
+                    signal, info, context,
+                );
+            }
+        }
+    }
+
+    fn write_all(mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            // SAFETY: `write` is async-signal-safe, and `bytes` is a valid slice.
+            let fd = OUTPUT_FD.load(Ordering::SeqCst);
+            let written = unsafe { libc::write(fd, bytes.as_ptr().cast(), bytes.len()) };
+            let Ok(written) = usize::try_from(written) else {
+                return;
+            };
+            if written == 0 {
+                return;
+            }
+            bytes = &bytes[written..];
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod signals {
+    pub fn install() {}
+}
@@ -1,149 +1,660 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use magnus::value::ReprValue;
-use magnus::{Error, Module};
+use magnus::{prelude::*, Error, Value};
 use ratatui::{
     backend::{CrosstermBackend, TestBackend},
-    Terminal,
+    crossterm::{event::KeyboardEnhancementFlags, Command},
+    layout::Rect,
+    Terminal, TerminalOptions, Viewport,
 };
+use std::borrow::Cow;
+use std::fmt;
 use std::io;
-use std::sync::Mutex;
+
+use crate::draw_stats::{ByteCount, CountingBackend, CountingWriter};
+use crate::hyperlink::HyperlinkBackend;
+use crate::output::Output;
+
+/// The backend drawing to the real terminal.
+pub type CrosstermTerminalBackend =
+    CountingBackend<HyperlinkBackend<CrosstermBackend<CountingWriter<Output>>>>;
+use std::sync::{Mutex, PoisonError};
 
 pub enum TerminalWrapper {
-    Crossterm(Terminal<CrosstermBackend<io::Stdout>>),
-    Test(Terminal<TestBackend>),
+    Crossterm(Terminal<CrosstermTerminalBackend>),
+    Test(Terminal<CountingBackend<TestBackend>>),
 }
 
 pub static TERMINAL: Mutex<Option<TerminalWrapper>> = Mutex::new(None);
 
-pub fn init_terminal(focus_events: bool, bracketed_paste: bool) -> Result<(), Error> {
+impl TerminalWrapper {
+    /// Returns the bytes written and cells drawn since the last call.
+    pub fn take_counts(&mut self) -> (u64, usize) {
+        match self {
+            TerminalWrapper::Crossterm(terminal) => terminal.backend_mut().take_counts(),
+            TerminalWrapper::Test(terminal) => terminal.backend_mut().take_counts(),
+        }
+    }
+}
+
+/// Returns a `RatatuiRuby::Error::Terminal` with `message`.
+pub(crate) fn terminal_error(message: impl Into<Cow<'static, str>>) -> Error {
     let ruby = magnus::Ruby::get().unwrap();
-    let mut term_lock = TERMINAL.lock().unwrap();
-    if term_lock.is_none() {
-        let module = ruby.define_module("RatatuiRuby")?;
-        let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-        let error_class = error_base.const_get("Terminal")?;
+    let error_class = ruby
+        .define_module("RatatuiRuby")
+        .and_then(|module| module.const_get::<_, magnus::RClass>("Error"))
+        .and_then(|error_base| error_base.const_get::<_, magnus::ExceptionClass>("Terminal"));
+    match error_class {
+        Ok(error_class) => Error::new(error_class, message),
+        Err(e) => e,
+    }
+}
 
-        ratatui::crossterm::terminal::enable_raw_mode()
-            .map_err(|e| Error::new(error_class, e.to_string()))?;
-        let mut stdout = io::stdout();
-        ratatui::crossterm::execute!(
-            stdout,
-            ratatui::crossterm::terminal::EnterAlternateScreen,
-            ratatui::crossterm::event::EnableMouseCapture
-        )
-        .map_err(|e| Error::new(error_class, e.to_string()))?;
+/// Which mouse events the terminal reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseCapture {
+    /// No mouse events. The terminal keeps its native text selection.
+    Off,
+    /// Button presses, releases, and the wheel.
+    Clicks,
+    /// Everything, including drags and movement.
+    All,
+}
+
+impl MouseCapture {
+    fn to_symbol(self, ruby: &magnus::Ruby) -> Value {
+        match self {
+            MouseCapture::Off => ruby.qfalse().as_value(),
+            MouseCapture::Clicks => ruby.to_symbol("clicks").as_value(),
+            MouseCapture::All => ruby.to_symbol("all").as_value(),
+        }
+    }
+}
+
+/// Enables normal mouse tracking without button-motion or any-motion tracking.
+struct EnableMouseClicks;
+
+impl Command for EnableMouseClicks {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[?1000h\x1b[?1015h\x1b[?1006h")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        // The Windows console cannot report clicks without movement.
+        ratatui::crossterm::event::EnableMouseCapture.execute_winapi()
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// Options the crossterm terminal was initialized with.
+///
+/// `restore_terminal` needs these to undo exactly what `init_terminal` did. The input
+/// modes change at runtime when the app toggles them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TerminalConfig {
+    pub mouse: MouseCapture,
+    pub focus_events: bool,
+    pub bracketed_paste: bool,
+    pub viewport: Viewport,
+    /// Kitty keyboard protocol flags pushed on init and popped on restore.
+    pub keyboard_enhancement: KeyboardEnhancementFlags,
+    /// Whether each frame is wrapped in a synchronized update (mode 2026).
+    pub synchronized_output: bool,
+    /// Where frames and mode changes are written.
+    pub output: Output,
+}
+
+impl TerminalConfig {
+    /// Only full-screen viewports use the alternate screen.
+    /// Inline and fixed viewports draw on the main screen so their output stays in scrollback.
+    pub fn uses_alternate_screen(&self) -> bool {
+        matches!(self.viewport, Viewport::Fullscreen)
+    }
+}
+
+static CONFIG: Mutex<Option<TerminalConfig>> = Mutex::new(None);
+
+/// Whether the crossterm terminal asked for key repeat and release events.
+///
+/// Without this flag, only press events are meaningful. Windows reports releases
+/// regardless, so they are dropped unless explicitly requested.
+pub fn reports_key_event_types() -> bool {
+    CONFIG.lock().unwrap().as_ref().is_some_and(|config| {
+        config
+            .keyboard_enhancement
+            .contains(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+    })
+}
+
+/// Whether the app asked for each frame to be wrapped in a synchronized update.
+fn synchronized_output() -> bool {
+    CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|config| config.synchronized_output)
+}
+
+/// Starts a synchronized update if the app asked for them, and returns whether it did.
+///
+/// Supporting terminals hold output until the update ends and present the frame at
+/// once. Others ignore the sequence.
+pub fn begin_synchronized_update(wrapper: &mut TerminalWrapper) -> io::Result<bool> {
+    let TerminalWrapper::Crossterm(terminal) = wrapper else {
+        return Ok(false);
+    };
+    let enabled = synchronized_output();
+    if enabled {
+        terminal.backend_mut().begin_synchronized_update()?;
+    }
+    Ok(enabled)
+}
 
-        if focus_events {
-            ratatui::crossterm::execute!(stdout, ratatui::crossterm::event::EnableFocusChange)
-                .map_err(|e| Error::new(error_class, e.to_string()))?;
+/// Like `begin_synchronized_update`, but the update starts once the frame is rendered,
+/// right before its cells are written.
+pub fn begin_synchronized_update_on_draw(wrapper: &mut TerminalWrapper) -> bool {
+    let TerminalWrapper::Crossterm(terminal) = wrapper else {
+        return false;
+    };
+    let enabled = synchronized_output();
+    if enabled {
+        terminal.backend_mut().begin_synchronized_update_on_draw();
+    }
+    enabled
+}
+
+pub fn end_synchronized_update(wrapper: &mut TerminalWrapper) -> io::Result<()> {
+    if let TerminalWrapper::Crossterm(terminal) = wrapper {
+        terminal.backend_mut().end_synchronized_update()?;
+    }
+    Ok(())
+}
+
+/// Converts the Ruby `mouse:` option. `true` means `:all`.
+pub fn parse_mouse_capture(value: Value) -> Result<MouseCapture, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if !value.to_bool() {
+        return Ok(MouseCapture::Off);
+    }
+    if value.is_kind_of(ruby.class_true_class()) {
+        return Ok(MouseCapture::All);
+    }
+    let mode: magnus::Symbol = magnus::Symbol::try_convert(value)?;
+    match mode.to_string().as_str() {
+        "clicks" => Ok(MouseCapture::Clicks),
+        "all" => Ok(MouseCapture::All),
+        other => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("Unknown mouse mode: {other}"),
+        )),
+    }
+}
+
+/// Converts the Ruby `keyboard_enhancement:` option into Kitty keyboard protocol flags.
+///
+/// `nil`/`false` disables the protocol, `true` enables every flag, and an Array
+/// of Symbols selects individual flags.
+pub fn parse_keyboard_enhancement(value: Value) -> Result<KeyboardEnhancementFlags, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if !value.to_bool() {
+        return Ok(KeyboardEnhancementFlags::empty());
+    }
+    if value.is_kind_of(ruby.class_true_class()) {
+        return Ok(KeyboardEnhancementFlags::all());
+    }
+
+    let names: Vec<magnus::Symbol> = Vec::try_convert(value)?;
+    let mut flags = KeyboardEnhancementFlags::empty();
+    for name in names {
+        flags |= match name.to_string().as_str() {
+            "disambiguate_escape_codes" => KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES,
+            "report_event_types" => KeyboardEnhancementFlags::REPORT_EVENT_TYPES,
+            "report_alternate_keys" => KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS,
+            "report_all_keys_as_escape_codes" => {
+                KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+            }
+            other => {
+                return Err(Error::new(
+                    ruby.exception_arg_error(),
+                    format!("Unknown keyboard enhancement flag: {other}"),
+                ))
+            }
+        };
+    }
+    Ok(flags)
+}
+
+/// Converts a Ruby `RatatuiRuby::Viewport` (or `nil`) into a ratatui `Viewport`.
+pub fn parse_viewport(value: Value) -> Result<Viewport, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if value.is_nil() {
+        return Ok(Viewport::Fullscreen);
+    }
+
+    let type_sym: magnus::Symbol = value.funcall("type", ())?;
+    match type_sym.to_string().as_str() {
+        "fullscreen" => Ok(Viewport::Fullscreen),
+        "inline" => {
+            let height: u16 = value.funcall("height", ())?;
+            Ok(Viewport::Inline(height))
         }
-        if bracketed_paste {
-            ratatui::crossterm::execute!(stdout, ratatui::crossterm::event::EnableBracketedPaste)
-                .map_err(|e| Error::new(error_class, e.to_string()))?;
+        "fixed" => {
+            let area: Value = value.funcall("area", ())?;
+            let x: u16 = area.funcall("x", ())?;
+            let y: u16 = area.funcall("y", ())?;
+            let width: u16 = area.funcall("width", ())?;
+            let height: u16 = area.funcall("height", ())?;
+            Ok(Viewport::Fixed(Rect::new(x, y, width, height)))
         }
+        other => Err(Error::new(
+            ruby.exception_arg_error(),
+            format!("Unknown viewport type: {other}"),
+        )),
+    }
+}
 
-        let backend = CrosstermBackend::new(stdout);
-        let terminal =
-            Terminal::new(backend).map_err(|e| Error::new(error_class, e.to_string()))?;
+pub fn init_terminal(
+    mouse: Value,
+    focus_events: bool,
+    bracketed_paste: bool,
+    viewport: Value,
+    keyboard_enhancement: Value,
+    color_depth: Value,
+    synchronized_output: bool,
+    output: Value,
+) -> Result<(), Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let mouse = parse_mouse_capture(mouse)?;
+    let viewport = parse_viewport(viewport)?;
+    let keyboard_enhancement = parse_keyboard_enhancement(keyboard_enhancement)?;
+    let color_depth = crate::color_depth::parse(color_depth)?;
+    let mut term_lock = TERMINAL.lock().unwrap();
+    if term_lock.is_none() {
+        let output = crate::output::parse(output)?;
+        // Crossterm asks for the cursor position on stdout, which would print the query
+        // into the app's result, and reads the answer from the wrong terminal.
+        if matches!(viewport, Viewport::Inline(_)) && output != Output::Stdout {
+            return Err(Error::new(
+                ruby.exception_arg_error(),
+                "Inline viewports can only draw on stdout",
+            ));
+        }
+        let config = TerminalConfig {
+            mouse,
+            focus_events,
+            bracketed_paste,
+            viewport,
+            keyboard_enhancement,
+            synchronized_output,
+            output,
+        };
+
+        enter_tui_mode(&config).map_err(|e| terminal_error(e.to_string()))?;
+        crate::teardown::entered(&config);
+        let terminal = crossterm_terminal(&config).map_err(|e| terminal_error(e.to_string()))?;
         *term_lock = Some(TerminalWrapper::Crossterm(terminal));
+        *CONFIG.lock().unwrap() = Some(config);
+        crate::color_depth::set(color_depth.unwrap_or_else(crate::color_depth::detect));
+        crate::event_source::set_tty_active(true);
     }
     Ok(())
 }
 
-pub fn init_test_terminal(width: u16, height: u16) -> Result<(), Error> {
+/// Switches the real terminal into TUI mode as described by `config`.
+fn enter_tui_mode(config: &TerminalConfig) -> io::Result<()> {
+    ratatui::crossterm::terminal::enable_raw_mode()?;
+    let mut out = config.output.clone();
+    if config.uses_alternate_screen() {
+        ratatui::crossterm::execute!(out, ratatui::crossterm::terminal::EnterAlternateScreen)?;
+    }
+    write_mouse_capture(&mut out, MouseCapture::Off, config.mouse)?;
+    if config.focus_events {
+        ratatui::crossterm::execute!(out, ratatui::crossterm::event::EnableFocusChange)?;
+    }
+    if config.bracketed_paste {
+        ratatui::crossterm::execute!(out, ratatui::crossterm::event::EnableBracketedPaste)?;
+    }
+    if !config.keyboard_enhancement.is_empty() {
+        ratatui::crossterm::execute!(
+            out,
+            ratatui::crossterm::event::PushKeyboardEnhancementFlags(config.keyboard_enhancement)
+        )?;
+    }
+    Ok(())
+}
+
+/// Undoes `enter_tui_mode`.
+///
+/// Errors are ignored so that every step runs, even if the terminal is half gone.
+fn leave_tui_mode(
+    terminal: &mut Terminal<CrosstermTerminalBackend>,
+    config: Option<&TerminalConfig>,
+) {
+    let _ = ratatui::crossterm::terminal::disable_raw_mode();
+    crate::cursor::leave(terminal.backend_mut());
+    if config.is_some_and(|config| !config.keyboard_enhancement.is_empty()) {
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::event::PopKeyboardEnhancementFlags
+        );
+    }
+    // Without a config, nothing says what was enabled, so disable everything.
+    if config.is_none_or(|config| config.mouse != MouseCapture::Off) {
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::event::DisableMouseCapture
+        );
+    }
+    if config.is_none_or(|config| config.focus_events) {
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::event::DisableFocusChange
+        );
+    }
+    if config.is_none_or(|config| config.bracketed_paste) {
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::event::DisableBracketedPaste
+        );
+    }
+    if config.is_none_or(TerminalConfig::uses_alternate_screen) {
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::terminal::LeaveAlternateScreen
+        );
+    } else {
+        // Leave the last frame in place and put the shell prompt on the line below it.
+        let area = terminal.get_frame().area();
+        let _ = ratatui::crossterm::execute!(
+            terminal.backend_mut(),
+            ratatui::crossterm::cursor::MoveTo(0, area.bottom().saturating_sub(1)),
+            ratatui::crossterm::style::Print("\r\n"),
+            ratatui::crossterm::cursor::Show
+        );
+    }
+}
+
+/// Switches mouse capture from `from` to `to`.
+fn write_mouse_capture(
+    out: &mut impl io::Write,
+    from: MouseCapture,
+    to: MouseCapture,
+) -> io::Result<()> {
+    if from == to {
+        return Ok(());
+    }
+    if from != MouseCapture::Off {
+        ratatui::crossterm::execute!(out, ratatui::crossterm::event::DisableMouseCapture)?;
+    }
+    match to {
+        MouseCapture::Off => Ok(()),
+        MouseCapture::Clicks => ratatui::crossterm::execute!(out, EnableMouseClicks),
+        MouseCapture::All => {
+            ratatui::crossterm::execute!(out, ratatui::crossterm::event::EnableMouseCapture)
+        }
+    }
+}
+
+/// Applies `change` to the recorded input modes and, if the terminal is in TUI mode,
+/// to the terminal itself. A suspended terminal picks the change up on resume.
+fn change_input_modes(
+    change: impl FnOnce(&mut TerminalConfig, &mut dyn io::Write) -> io::Result<()>,
+) -> Result<(), Error> {
+    let mut term_lock = TERMINAL.lock().unwrap();
+    let mut config_lock = CONFIG.lock().unwrap();
+    let Some(config) = config_lock.as_mut() else {
+        return Err(terminal_error("Terminal is not initialized"));
+    };
+    let result = match term_lock.as_mut() {
+        Some(TerminalWrapper::Crossterm(t)) if crate::teardown::is_active() => {
+            change(config, t.backend_mut())
+        }
+        _ => change(config, &mut io::sink()),
+    };
+    result.map_err(|e| terminal_error(e.to_string()))
+}
+
+pub fn set_mouse_capture(mode: Value) -> Result<(), Error> {
+    let mode = parse_mouse_capture(mode)?;
+    change_input_modes(|config, mut out| {
+        write_mouse_capture(&mut out, config.mouse, mode)?;
+        config.mouse = mode;
+        Ok(())
+    })
+}
+
+pub fn set_focus_events(enabled: bool) -> Result<(), Error> {
+    change_input_modes(|config, mut out| {
+        if config.focus_events != enabled {
+            if enabled {
+                ratatui::crossterm::execute!(
+                    &mut out,
+                    ratatui::crossterm::event::EnableFocusChange
+                )?;
+            } else {
+                ratatui::crossterm::execute!(
+                    &mut out,
+                    ratatui::crossterm::event::DisableFocusChange
+                )?;
+            }
+            config.focus_events = enabled;
+        }
+        Ok(())
+    })
+}
+
+pub fn set_bracketed_paste(enabled: bool) -> Result<(), Error> {
+    change_input_modes(|config, mut out| {
+        if config.bracketed_paste != enabled {
+            if enabled {
+                ratatui::crossterm::execute!(
+                    &mut out,
+                    ratatui::crossterm::event::EnableBracketedPaste
+                )?;
+            } else {
+                ratatui::crossterm::execute!(
+                    &mut out,
+                    ratatui::crossterm::event::DisableBracketedPaste
+                )?;
+            }
+            config.bracketed_paste = enabled;
+        }
+        Ok(())
+    })
+}
+
+/// Returns the current input modes as `{ mouse:, focus_events:, bracketed_paste: }`.
+pub fn input_modes() -> Result<magnus::RHash, Error> {
     let ruby = magnus::Ruby::get().unwrap();
+    let config = CONFIG.lock().unwrap().clone();
+    let hash = ruby.hash_new();
+    if let Some(config) = config {
+        hash.aset(ruby.to_symbol("mouse"), config.mouse.to_symbol(&ruby))?;
+        hash.aset(ruby.to_symbol("focus_events"), config.focus_events)?;
+        hash.aset(ruby.to_symbol("bracketed_paste"), config.bracketed_paste)?;
+    }
+    Ok(hash)
+}
+
+fn crossterm_terminal(config: &TerminalConfig) -> io::Result<Terminal<CrosstermTerminalBackend>> {
+    let options = TerminalOptions {
+        viewport: config.viewport.clone(),
+    };
+    let bytes = ByteCount::default();
+    let writer = CountingWriter::new(config.output.clone(), bytes.clone());
+    Terminal::with_options(
+        CountingBackend::with_bytes(HyperlinkBackend::new(CrosstermBackend::new(writer)), bytes),
+        options,
+    )
+}
+
+pub fn init_test_terminal(
+    width: u16,
+    height: u16,
+    viewport: Value,
+    color_depth: Value,
+) -> Result<(), Error> {
+    let viewport = parse_viewport(viewport)?;
+    // The environment running the tests says nothing about the terminal being simulated.
+    let color_depth = crate::color_depth::parse(color_depth)?
+        .unwrap_or(crate::color_depth::ColorDepth::TrueColor);
     let mut term_lock = TERMINAL.lock().unwrap();
-    let backend = TestBackend::new(width, height);
-    let module = ruby.define_module("RatatuiRuby")?;
-    let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-    let error_class = error_base.const_get("Terminal")?;
-    let terminal = Terminal::new(backend).map_err(|e| Error::new(error_class, e.to_string()))?;
+    let backend = CountingBackend::new(TestBackend::new(width, height));
+    let terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: viewport.clone(),
+        },
+    )
+    .map_err(|e| terminal_error(e.to_string()))?;
     *term_lock = Some(TerminalWrapper::Test(terminal));
+    crate::cursor::reset();
+    crate::osc::reset();
+    crate::hyperlink::reset();
+    crate::color_depth::set(color_depth);
+    crate::draw_stats::reset();
+    // Nothing is enabled on a test terminal until the app toggles it.
+    *CONFIG.lock().unwrap() = Some(TerminalConfig {
+        mouse: MouseCapture::Off,
+        focus_events: false,
+        bracketed_paste: false,
+        viewport,
+        keyboard_enhancement: KeyboardEnhancementFlags::empty(),
+        synchronized_output: false,
+        output: Output::Stdout,
+    });
     Ok(())
 }
 
 pub fn restore_terminal() {
-    let mut term_lock = TERMINAL.lock().unwrap();
-    if let Some(wrapper) = term_lock.take() {
+    // A panic while drawing poisons the locks. Restoring the terminal matters more.
+    let mut term_lock = TERMINAL.lock().unwrap_or_else(PoisonError::into_inner);
+    let config = CONFIG.lock().unwrap_or_else(PoisonError::into_inner).take();
+    if let Some(mut wrapper) = term_lock.take() {
+        crate::recording::reset();
+        crate::osc::restore(&mut wrapper);
         match wrapper {
             TerminalWrapper::Crossterm(mut t) => {
-                let _ = ratatui::crossterm::terminal::disable_raw_mode();
-                let _ = ratatui::crossterm::execute!(
-                    t.backend_mut(),
-                    ratatui::crossterm::terminal::LeaveAlternateScreen,
-                    ratatui::crossterm::event::DisableMouseCapture,
-                    ratatui::crossterm::event::DisableFocusChange,
-                    ratatui::crossterm::event::DisableBracketedPaste
-                );
+                if crate::teardown::leave() {
+                    leave_tui_mode(&mut t, config.as_ref());
+                }
+                crate::event_source::set_tty_active(false);
             }
             TerminalWrapper::Test(_) => {}
         }
+        crate::cursor::reset();
+        crate::hyperlink::reset();
+        crate::color_depth::reset();
+        crate::draw_stats::reset();
+    }
+}
+
+/// Temporarily leaves TUI mode, keeping the terminal and its options for `resume_terminal`.
+pub fn suspend_terminal() -> Result<(), Error> {
+    let mut term_lock = TERMINAL.lock().unwrap();
+    match term_lock.as_mut() {
+        Some(TerminalWrapper::Crossterm(t)) => {
+            if crate::teardown::leave() {
+                leave_tui_mode(t, CONFIG.lock().unwrap().as_ref());
+                crate::event_source::set_tty_active(false);
+            }
+            Ok(())
+        }
+        Some(TerminalWrapper::Test(_)) => Ok(()),
+        None => Err(terminal_error("Terminal is not initialized")),
     }
 }
 
+/// Re-enters TUI mode with the options `init_terminal` received.
+///
+/// The screen may have been scribbled on meanwhile, so the next draw repaints every cell.
+pub fn resume_terminal() -> Result<(), Error> {
+    let mut term_lock = TERMINAL.lock().unwrap();
+    match term_lock.as_mut() {
+        Some(TerminalWrapper::Crossterm(t)) => {
+            let config = CONFIG.lock().unwrap().clone();
+            let Some(config) = config else {
+                return Ok(());
+            };
+            if crate::teardown::is_active() {
+                return Ok(());
+            }
+            enter_tui_mode(&config).map_err(|e| terminal_error(e.to_string()))?;
+            crate::teardown::entered(&config);
+            // A new terminal picks up size changes and, for inline viewports, the new cursor row.
+            *t = crossterm_terminal(&config).map_err(|e| terminal_error(e.to_string()))?;
+            t.clear().map_err(|e| terminal_error(e.to_string()))?;
+            crate::event_source::set_tty_active(true);
+            Ok(())
+        }
+        Some(TerminalWrapper::Test(t)) => t.clear().map_err(|e| terminal_error(e.to_string())),
+        None => Err(terminal_error("Terminal is not initialized")),
+    }
+}
+
+/// Returns the symbols of every cell, one line per row.
+pub fn buffer_content(buffer: &ratatui::buffer::Buffer) -> String {
+    let area = buffer.area;
+    let mut result = String::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            result.push_str(cell.symbol());
+        }
+        result.push('\n');
+    }
+    result
+}
+
 pub fn get_buffer_content() -> Result<String, Error> {
-    let ruby = magnus::Ruby::get().unwrap();
     let term_lock = TERMINAL.lock().unwrap();
     if let Some(TerminalWrapper::Test(terminal)) = term_lock.as_ref() {
-        let buffer = terminal.backend().buffer();
-        let area = buffer.area;
-        let mut result = String::new();
-        for y in 0..area.height {
-            for x in 0..area.width {
-                let cell = buffer.cell((x, y)).unwrap();
-                result.push_str(cell.symbol());
-            }
-            result.push('\n');
-        }
-        Ok(result)
+        Ok(buffer_content(terminal.backend().buffer()))
     } else {
-        let module = ruby.define_module("RatatuiRuby")?;
-        let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-        let error_class = error_base.const_get("Terminal")?;
-        Err(Error::new(
-            error_class,
-            "Terminal is not initialized as TestBackend",
+        Err(terminal_error("Terminal is not initialized as TestBackend"))
+    }
+}
+
+/// Returns a copy of the test terminal's buffer.
+pub fn get_buffer() -> Result<crate::buffer::RubyBuffer, Error> {
+    let term_lock = TERMINAL.lock().unwrap();
+    if let Some(TerminalWrapper::Test(terminal)) = term_lock.as_ref() {
+        Ok(crate::buffer::RubyBuffer::copy_of(
+            terminal.backend().buffer(),
         ))
+    } else {
+        Err(terminal_error("Terminal is not initialized as TestBackend"))
     }
 }
 
 pub fn get_cursor_position() -> Result<Option<(u16, u16)>, Error> {
-    let ruby = magnus::Ruby::get().unwrap();
     let mut term_lock = TERMINAL.lock().unwrap();
     if let Some(TerminalWrapper::Test(terminal)) = term_lock.as_mut() {
-        let module = ruby.define_module("RatatuiRuby")?;
-        let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-        let error_class = error_base.const_get("Terminal")?;
         let pos = terminal
             .get_cursor_position()
-            .map_err(|e| Error::new(error_class, e.to_string()))?;
+            .map_err(|e| terminal_error(e.to_string()))?;
         Ok(Some(pos.into()))
     } else {
-        let module = ruby.define_module("RatatuiRuby")?;
-        let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-        let error_class = error_base.const_get("Terminal")?;
-        Err(Error::new(
-            error_class,
-            "Terminal is not initialized as TestBackend",
-        ))
+        Err(terminal_error("Terminal is not initialized as TestBackend"))
     }
 }
 
 pub fn resize_terminal(width: u16, height: u16) -> Result<(), Error> {
-    let ruby = magnus::Ruby::get().unwrap();
     let mut term_lock = TERMINAL.lock().unwrap();
     if let Some(wrapper) = term_lock.as_mut() {
         match wrapper {
             TerminalWrapper::Crossterm(_) => {}
             TerminalWrapper::Test(terminal) => {
                 terminal.backend_mut().resize(width, height);
+                crate::recording::resize(width, height);
                 if let Err(e) = terminal.resize(ratatui::layout::Rect::new(0, 0, width, height)) {
-                    let module = ruby.define_module("RatatuiRuby")?;
-                    let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-                    let error_class = error_base.const_get("Terminal")?;
-                    return Err(Error::new(error_class, e.to_string()));
+                    return Err(terminal_error(e.to_string()));
                 }
             }
         }
@@ -151,41 +662,44 @@ pub fn resize_terminal(width: u16, height: u16) -> Result<(), Error> {
     Ok(())
 }
 
-use magnus::Value;
+/// Returns the cell at `(x, y)` as the Hash `Buffer::Cell` is built from.
+pub fn cell_at(
+    buffer: &ratatui::buffer::Buffer,
+    x: u16,
+    y: u16,
+    links: &crate::hyperlink::LinkTargets,
+) -> Result<magnus::RHash, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    if let Some(cell) = buffer.cell((x, y)) {
+        let hash = ruby.hash_new();
+        hash.aset("char", cell.symbol())?;
+        hash.aset("fg", color_to_value(cell.fg))?;
+        hash.aset("bg", color_to_value(cell.bg))?;
+        hash.aset("modifiers", modifiers_to_value(cell.modifier))?;
+        hash.aset("hyperlink", links.url(cell))?;
+        Ok(hash)
+    } else {
+        Err(terminal_error(format!(
+            "Coordinates ({x}, {y}) out of bounds"
+        )))
+    }
+}
 
 pub fn get_cell_at(x: u16, y: u16) -> Result<magnus::RHash, Error> {
-    let ruby = magnus::Ruby::get().unwrap();
     let term_lock = TERMINAL.lock().unwrap();
     if let Some(TerminalWrapper::Test(terminal)) = term_lock.as_ref() {
-        let buffer = terminal.backend().buffer();
-        if let Some(cell) = buffer.cell((x, y)) {
-            let hash = ruby.hash_new();
-            hash.aset("char", cell.symbol())?;
-            hash.aset("fg", color_to_value(cell.fg))?;
-            hash.aset("bg", color_to_value(cell.bg))?;
-            hash.aset("modifiers", modifiers_to_value(cell.modifier))?;
-            Ok(hash)
-        } else {
-            let module = ruby.define_module("RatatuiRuby")?;
-            let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-            let error_class = error_base.const_get("Terminal")?;
-            Err(Error::new(
-                error_class,
-                format!("Coordinates ({x}, {y}) out of bounds"),
-            ))
-        }
+        cell_at(
+            terminal.backend().buffer(),
+            x,
+            y,
+            &crate::hyperlink::LinkTargets::Shared,
+        )
     } else {
-        let module = ruby.define_module("RatatuiRuby")?;
-        let error_base = module.const_get::<_, magnus::RClass>("Error")?;
-        let error_class = error_base.const_get("Terminal")?;
-        Err(Error::new(
-            error_class,
-            "Terminal is not initialized as TestBackend",
-        ))
+        Err(terminal_error("Terminal is not initialized as TestBackend"))
     }
 }
 
-fn color_to_value(color: ratatui::style::Color) -> Value {
+pub fn color_to_value(color: ratatui::style::Color) -> Value {
     let ruby = magnus::Ruby::get().unwrap();
     match color {
         ratatui::style::Color::Reset => ruby.qnil().as_value(),
@@ -212,7 +726,7 @@ fn color_to_value(color: ratatui::style::Color) -> Value {
     }
 }
 
-fn modifiers_to_value(modifier: ratatui::style::Modifier) -> Value {
+pub fn modifiers_to_value(modifier: ratatui::style::Modifier) -> Value {
     let ruby = magnus::Ruby::get().unwrap();
     let ary = ruby.ary_new();
 
@@ -0,0 +1,465 @@
+// SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Asking the terminal for its colors (OSC 10, 11, and 4).
+//!
+//! Terminals answer on their input stream, where crossterm would parse each reply as
+//! Alt+`]` followed by a run of key presses. Instead, the queries end with a Primary
+//! Device Attributes request (DA1), which every terminal answers, and the replies are
+//! read straight from the tty until that answer arrives or the timeout expires. A
+//! query the terminal does not support simply gets no reply.
+//!
+//! Events crossterm already parsed are moved to the event queue first, and keys typed
+//! while waiting are queued after them, so `poll_event` still sees input in order.
+//! Crossterm's parser is private, so typed keys are decoded here: characters, Alt and
+//! Ctrl combinations, and the CSI and SS3 sequences of arrows, Home, End, Insert,
+//! Delete, Page Up, Page Down, Back Tab, and F1 to F12. Anything else typed during the
+//! query (mouse reports, pastes, Kitty protocol keys) is lost.
+
+use magnus::{Error, RHash};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::terminal::{terminal_error, TerminalWrapper, TERMINAL};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Colors {
+    foreground: Option<[u8; 3]>,
+    background: Option<[u8; 3]>,
+    palette: Vec<(u8, [u8; 3])>,
+}
+
+/// Returns `{ foreground:, background:, palette: { index => color } }` with colors as
+/// `"#rrggbb"` Strings, or `nil` where the terminal did not answer.
+pub fn query_terminal_colors(palette: Vec<u8>, timeout: f64) -> Result<RHash, Error> {
+    let ruby = magnus::Ruby::get().unwrap();
+    let timeout = Duration::try_from_secs_f64(timeout)
+        .map_err(|e| Error::new(ruby.exception_arg_error(), format!("Invalid timeout: {e}")))?;
+    let simulated = matches!(
+        TERMINAL.lock().unwrap().as_ref(),
+        Some(TerminalWrapper::Test(_))
+    );
+    let colors = if simulated {
+        // The test backend has no terminal to ask.
+        Colors::default()
+    } else {
+        imp::query(&palette, timeout).map_err(|e| terminal_error(e.to_string()))?
+    };
+
+    let hex = |rgb: Option<[u8; 3]>| rgb.map(|[r, g, b]| format!("#{r:02x}{g:02x}{b:02x}"));
+    let entries = ruby.hash_new();
+    for index in palette {
+        let rgb = colors
+            .palette
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, rgb)| *rgb);
+        entries.aset(index, hex(rgb))?;
+    }
+    let hash = ruby.hash_new();
+    hash.aset(ruby.to_symbol("foreground"), hex(colors.foreground))?;
+    hash.aset(ruby.to_symbol("background"), hex(colors.background))?;
+    hash.aset(ruby.to_symbol("palette"), entries)?;
+    Ok(hash)
+}
+
+/// The queries for `palette`, followed by DA1.
+fn query_sequence(palette: &[u8]) -> String {
+    let mut sequence = String::from("\x1b]10;?\x07\x1b]11;?\x07");
+    for index in palette {
+        let _ = write!(sequence, "\x1b]4;{index};?\x07");
+    }
+    sequence.push_str("\x1b[c");
+    sequence
+}
+
+/// Consumes complete replies and keys from the front of `input`.
+///
+/// Returns the number of bytes consumed and whether the DA1 reply was seen. An
+/// incomplete sequence at the end is left for the next read.
+fn parse_input(input: &[u8], colors: &mut Colors, keys: &mut Vec<Event>) -> (usize, bool) {
+    let mut i = 0;
+    while i < input.len() {
+        match &input[i..] {
+            [0x1b, b']', body @ ..] => {
+                let Some((len, terminator)) = osc_end(body) else {
+                    return (i, false);
+                };
+                parse_osc(&body[..len], colors);
+                i += 2 + len + terminator;
+            }
+            [0x1b, b'[', body @ ..] => {
+                let Some(len) = body.iter().position(|b| (0x40..=0x7e).contains(b)) else {
+                    return (i, false);
+                };
+                i += 2 + len + 1;
+                if body.first() == Some(&b'?') && body[len] == b'c' {
+                    return (i, true);
+                }
+                keys.extend(escape_key(body[len], &body[..len]));
+            }
+            [0x1b, b'O', final_byte, ..] => {
+                keys.extend(escape_key(*final_byte, &[]));
+                i += 3;
+            }
+            [0x1b] | [0x1b, b'O'] => return (i, false),
+            [0x1b, rest @ ..] => {
+                let Some((len, c)) = next_char(rest) else {
+                    return (i, false);
+                };
+                keys.extend(key_event(c).map(with_alt));
+                i += 1 + len;
+            }
+            rest => {
+                let Some((len, c)) = next_char(rest) else {
+                    return (i, false);
+                };
+                keys.extend(key_event(c));
+                i += len;
+            }
+        }
+    }
+    (i, false)
+}
+
+/// Decodes the UTF-8 character at the start of `input`, with its length in bytes.
+///
+/// Returns `None` if the character is incomplete. Invalid bytes decode as NUL, which
+/// `key_event` ignores.
+fn next_char(input: &[u8]) -> Option<(usize, char)> {
+    let len = match input[0] {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    };
+    let bytes = input.get(..len)?;
+    let c = std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .unwrap_or('\0');
+    Some((len, c))
+}
+
+/// Finds the end of an OSC body: its length and the length of its terminator (BEL or ST).
+fn osc_end(body: &[u8]) -> Option<(usize, usize)> {
+    for (j, &b) in body.iter().enumerate() {
+        match b {
+            0x07 => return Some((j, 1)),
+            0x1b => {
+                return match body.get(j + 1) {
+                    Some(b'\\') => Some((j, 2)),
+                    // Not a terminator: end here and leave the ESC for the next sequence.
+                    Some(_) => Some((j, 0)),
+                    None => None,
+                };
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_osc(body: &[u8], colors: &mut Colors) {
+    let Ok(body) = std::str::from_utf8(body) else {
+        return;
+    };
+    let mut parts = body.split(';');
+    match parts.next() {
+        Some("10") => colors.foreground = parts.next().and_then(parse_rgb),
+        Some("11") => colors.background = parts.next().and_then(parse_rgb),
+        Some("4") => {
+            let index = parts.next().and_then(|i| i.parse::<u8>().ok());
+            let rgb = parts.next().and_then(parse_rgb);
+            if let (Some(index), Some(rgb)) = (index, rgb) {
+                colors.palette.push((index, rgb));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses an X11 color spec (`rgb:r/g/b`), with 1 to 4 hex digits per channel.
+fn parse_rgb(spec: &str) -> Option<[u8; 3]> {
+    let channels = spec
+        .strip_prefix("rgb:")
+        .or_else(|| spec.strip_prefix("rgba:"))?;
+    let mut channels = channels.split('/');
+    let mut rgb = [0; 3];
+    for value in &mut rgb {
+        let hex = channels.next()?;
+        if hex.is_empty() || hex.len() > 4 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let max = (1u32 << (4 * hex.len())) - 1;
+        let scaled = (u32::from_str_radix(hex, 16).ok()? * 255 + max / 2) / max;
+        *value = u8::try_from(scaled).ok()?;
+    }
+    Some(rgb)
+}
+
+/// The key event crossterm would report for a character typed while waiting.
+fn key_event(c: char) -> Option<Event> {
+    let (code, modifiers) = match c {
+        '\r' | '\n' => (KeyCode::Enter, KeyModifiers::NONE),
+        '\t' => (KeyCode::Tab, KeyModifiers::NONE),
+        '\x7f' | '\x08' => (KeyCode::Backspace, KeyModifiers::NONE),
+        '\x1b' => (KeyCode::Esc, KeyModifiers::NONE),
+        '\x01'..='\x1a' => {
+            let letter = char::from_u32(u32::from(c) - 1 + u32::from('a'))?;
+            (KeyCode::Char(letter), KeyModifiers::CONTROL)
+        }
+        c if c.is_control() => return None,
+        c if c.is_uppercase() => (KeyCode::Char(c), KeyModifiers::SHIFT),
+        c => (KeyCode::Char(c), KeyModifiers::NONE),
+    };
+    Some(Event::Key(KeyEvent::new(code, modifiers)))
+}
+
+fn with_alt(event: Event) -> Event {
+    match event {
+        Event::Key(key) => Event::Key(KeyEvent::new(key.code, key.modifiers | KeyModifiers::ALT)),
+        event => event,
+    }
+}
+
+/// The key event crossterm would report for a CSI or SS3 sequence, given its final byte
+/// and parameters, or `None` for sequences that are not keys decoded here.
+fn escape_key(final_byte: u8, params: &[u8]) -> Option<Event> {
+    let params = std::str::from_utf8(params).ok()?;
+    let mut params = params.split(';');
+    let first = params.next().unwrap_or("");
+    let modifier_param: u8 = params.next().map_or(Some(1), |m| m.parse().ok())?;
+    let bits = modifier_param.checked_sub(1)?;
+    let mut modifiers = KeyModifiers::NONE;
+    for (bit, modifier) in [
+        (1, KeyModifiers::SHIFT),
+        (2, KeyModifiers::ALT),
+        (4, KeyModifiers::CONTROL),
+        (8, KeyModifiers::SUPER),
+        (16, KeyModifiers::HYPER),
+        (32, KeyModifiers::META),
+    ] {
+        if bits & bit != 0 {
+            modifiers |= modifier;
+        }
+    }
+    let code = match final_byte {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'Z' => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::BackTab
+        }
+        b'P'..=b'S' => KeyCode::F(final_byte - b'P' + 1),
+        b'~' => match first.parse::<u8>().ok()? {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            n @ 11..=15 => KeyCode::F(n - 10),
+            n @ 17..=21 => KeyCode::F(n - 11),
+            n @ 23..=24 => KeyCode::F(n - 12),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Event::Key(KeyEvent::new(code, modifiers)))
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{key_event, parse_input, query_sequence, Colors};
+    use ratatui::crossterm::event::{self, Event, KeyEventKind};
+    use ratatui::crossterm::terminal;
+    use std::fs::{File, OpenOptions};
+    use std::io::{self, Read, Write};
+    use std::os::fd::AsRawFd;
+    use std::time::{Duration, Instant};
+
+    /// Asks the controlling terminal for its colors, entering raw mode for the duration
+    /// if needed. Without a controlling terminal, every color is unknown.
+    pub fn query(palette: &[u8], timeout: Duration) -> io::Result<Colors> {
+        let Ok(mut tty) = OpenOptions::new().read(true).write(true).open("/dev/tty") else {
+            return Ok(Colors::default());
+        };
+        let raw = terminal::is_raw_mode_enabled()?;
+        if raw {
+            // Crossterm only reads during `poll_event`, so what it holds came before the query.
+            let mut events = Vec::new();
+            while event::poll(Duration::ZERO)? {
+                match event::read()? {
+                    // `poll_event` drops these unless the app asked for them.
+                    Event::Key(key)
+                        if key.kind != KeyEventKind::Press
+                            && !crate::terminal::reports_key_event_types() => {}
+                    event => events.push(event),
+                }
+            }
+            crate::events::queue_events(events);
+        } else {
+            terminal::enable_raw_mode()?;
+        }
+        let result = exchange(&mut tty, palette, timeout);
+        if !raw {
+            let _ = terminal::disable_raw_mode();
+        }
+        crate::event_source::refresh();
+        result
+    }
+
+    fn exchange(tty: &mut File, palette: &[u8], timeout: Duration) -> io::Result<Colors> {
+        // Frames still buffered for stdout must not land after the queries.
+        io::stdout().flush()?;
+        tty.write_all(query_sequence(palette).as_bytes())?;
+        tty.flush()?;
+
+        let deadline = Instant::now() + timeout;
+        let mut input = Vec::new();
+        let mut colors = Colors::default();
+        let mut keys = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || !readable(tty, remaining) {
+                break;
+            }
+            let mut chunk = [0; 1024];
+            let n = tty.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            input.extend_from_slice(&chunk[..n]);
+            let (consumed, done) = parse_input(&input, &mut colors, &mut keys);
+            input.drain(..consumed);
+            if done {
+                break;
+            }
+        }
+        if input == [0x1b] {
+            keys.extend(key_event('\x1b'));
+        }
+        crate::events::queue_events(keys);
+        Ok(colors)
+    }
+
+    fn readable(tty: &File, timeout: Duration) -> bool {
+        let mut pollfd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = i32::try_from(timeout.as_millis())
+            .unwrap_or(i32::MAX)
+            .max(1);
+        loop {
+            // SAFETY: `pollfd` is a valid array of one `pollfd` for the duration of the call.
+            let ready = unsafe { libc::poll(&raw mut pollfd, 1, timeout_ms) };
+            if ready < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return ready > 0 && pollfd.revents & libc::POLLIN != 0;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use super::Colors;
+    use std::io;
+    use std::time::Duration;
+
+    /// The Windows console does not report its colors through escape sequences.
+    pub fn query(_palette: &[u8], _timeout: Duration) -> io::Result<Colors> {
+        Ok(Colors::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(parse_rgb("rgb:ffff/8080/0000"), Some([255, 128, 0]));
+        assert_eq!(parse_rgb("rgb:f/8/0"), Some([255, 136, 0]));
+        assert_eq!(parse_rgb("rgba:1e1e/1e1e/2e2e/ffff"), Some([30, 30, 46]));
+        assert_eq!(parse_rgb("#ffffff"), None);
+        assert_eq!(parse_rgb("rgb:ff/ff"), None);
+    }
+
+    #[test]
+    fn test_parse_replies_until_device_attributes() {
+        let input = b"\x1b]10;rgb:0000/0000/0000\x1b\\\x1b]11;rgb:ffff/ffff/ffff\x07\
+                      \x1b]4;1;rgb:cdcd/0000/0000\x07\x1b[?62;22cq";
+        let mut colors = Colors::default();
+        let mut keys = Vec::new();
+        let (consumed, done) = parse_input(input, &mut colors, &mut keys);
+        assert!(done);
+        assert_eq!(consumed, input.len() - 1);
+        assert_eq!(colors.foreground, Some([0, 0, 0]));
+        assert_eq!(colors.background, Some([255, 255, 255]));
+        assert_eq!(colors.palette, vec![(1, [205, 0, 0])]);
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_parse_keeps_typed_keys_and_partial_replies() {
+        let input = b"aB\r\x03\x1b]11;rgb:00";
+        let mut colors = Colors::default();
+        let mut keys = Vec::new();
+        let (consumed, done) = parse_input(input, &mut colors, &mut keys);
+        assert!(!done);
+        assert_eq!(consumed, 4);
+        assert_eq!(
+            keys,
+            vec![
+                Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE)),
+                Event::Key(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT)),
+                Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+                Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_keeps_typed_escape_sequences() {
+        let input = b"\x1b[A\x1b[1;5C\x1bOP\x1b[3~\x1b[15;2~\x1b[Z\x1bx\x1b[<0;1;1M\x1b[?1;2c";
+        let mut colors = Colors::default();
+        let mut keys = Vec::new();
+        let (consumed, done) = parse_input(input, &mut colors, &mut keys);
+        assert!(done);
+        assert_eq!(consumed, input.len());
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            keys,
+            vec![
+                key(KeyCode::Up, KeyModifiers::NONE),
+                key(KeyCode::Right, KeyModifiers::CONTROL),
+                key(KeyCode::F(1), KeyModifiers::NONE),
+                key(KeyCode::Delete, KeyModifiers::NONE),
+                key(KeyCode::F(5), KeyModifiers::SHIFT),
+                key(KeyCode::BackTab, KeyModifiers::SHIFT),
+                key(KeyCode::Char('x'), KeyModifiers::ALT),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_waits_for_incomplete_escape_sequences() {
+        for input in [&b"\x1bO"[..], b"\x1b[1;5", b"\x1b\xc3"] {
+            let mut keys = Vec::new();
+            assert_eq!(
+                parse_input(input, &mut Colors::default(), &mut keys),
+                (0, false)
+            );
+            assert!(keys.is_empty());
+        }
+    }
+}
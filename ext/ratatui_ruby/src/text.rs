@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::hyperlink;
 use crate::style::parse_style;
 use magnus::{prelude::*, Error, Value};
 use ratatui::text::{Line, Span};
@@ -107,9 +108,14 @@ pub fn parse_span(value: Value) -> Result<Span<'static>, Error> {
     // Extract content and style from the Ruby Span
     let content: Value = value.funcall("content", ())?;
     let style_val: Value = value.funcall("style", ())?;
+    let hyperlink_val: Value = value.funcall("hyperlink", ())?;
 
     let content_str: String = content.funcall("to_s", ())?;
-    let style = parse_style(style_val)?;
+    let mut style = parse_style(style_val)?;
+    if !hyperlink_val.is_nil() {
+        let url: String = hyperlink_val.funcall("to_s", ())?;
+        style = style.patch(hyperlink::style_for(&url));
+    }
 
     Ok(Span::styled(content_str, style))
 }
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_bar_set, parse_block, parse_style};
 use crate::text::{parse_line, parse_span};
 use bumpalo::Bump;
@@ -142,7 +143,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         bar_chart = bar_chart.bar_set(parse_bar_set(bar_set_val, &bump)?);
     }
 
-    frame.render_widget(bar_chart, area);
+    draw_stats::render_widget(frame, bar_chart, area);
     Ok(())
 }
 
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::rendering::render_node;
 use crate::style::parse_block;
 use bumpalo::Bump;
@@ -13,7 +14,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let block_clone = block.clone();
 
     // Render the block itself (borders, styling)
-    block_clone.render(area, frame.buffer_mut());
+    draw_stats::timed(|| block_clone.render(area, frame.buffer_mut()));
 
     // Get children and render them within the block's inner area
     let children_val: Value = node.funcall("children", ())?;
@@ -30,9 +31,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
                 let index = isize::try_from(i)
                     .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
                 let child: Value = arr.entry(index)?;
-                if let Err(e) = render_node(frame, inner, child) {
-                    eprintln!("Error rendering block child {i}: {e:?}");
-                }
+                render_node(frame, inner, child)?;
             }
         }
     }
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Value};
@@ -78,6 +79,6 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         calendar = calendar.block(parse_block(block_val, &bump)?);
     }
 
-    frame.render_widget(calendar, area);
+    draw_stats::render_widget(frame, calendar, area);
     Ok(())
 }
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_color, parse_style};
 use crate::text::parse_text;
 use bumpalo::Bump;
@@ -139,7 +140,7 @@ pub fn render(frame: &mut Frame, area: ratatui::layout::Rect, node: Value) -> Re
         }
     });
 
-    frame.render_widget(canvas, area);
+    draw_stats::render_widget(frame, canvas, area);
     Ok(())
 }
 
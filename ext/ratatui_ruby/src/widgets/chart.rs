@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Symbol, Value};
@@ -15,13 +16,6 @@ use ratatui::{
 pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let bump = Bump::new();
     let ruby = magnus::Ruby::get().unwrap();
-    // SAFETY: Immediate conversion to owned string avoids GC-unsafe borrowed reference.
-    let class_name = unsafe { node.class().name() }.into_owned();
-
-    if class_name == "RatatuiRuby::LineChart" {
-        return render_line_chart(frame, area, node);
-    }
-
     let datasets_val: magnus::RArray = node.funcall("datasets", ())?;
     let x_axis_val: Value = node.funcall("x_axis", ())?;
     let y_axis_val: Value = node.funcall("y_axis", ())?;
@@ -126,7 +120,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         }
     }
 
-    frame.render_widget(chart, area);
+    draw_stats::render_widget(frame, chart, area);
     Ok(())
 }
 
@@ -167,7 +161,7 @@ fn parse_axis(axis_val: Value) -> Result<Axis<'static>, Error> {
     Ok(axis)
 }
 
-fn render_line_chart(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
+pub fn render_line_chart(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let bump = Bump::new();
     let ruby = magnus::Ruby::get().unwrap();
     let datasets_val: magnus::RArray = node.funcall("datasets", ())?;
@@ -274,7 +268,7 @@ fn render_line_chart(frame: &mut Frame, area: Rect, node: Value) -> Result<(), E
         chart = chart.block(parse_block(block_val, &bump)?);
     }
 
-    frame.render_widget(chart, area);
+    draw_stats::render_widget(frame, chart, area);
     Ok(())
 }
 
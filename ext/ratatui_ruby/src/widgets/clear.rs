@@ -1,19 +1,20 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Value};
 use ratatui::{layout::Rect, widgets::Widget, Frame};
 
 pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
-    frame.render_widget(ratatui::widgets::Clear, area);
+    draw_stats::render_widget(frame, ratatui::widgets::Clear, area);
 
     // If a block is provided, render it on top of the cleared area
     if let Ok(block_val) = node.funcall::<_, _, Value>("block", ()) {
         if !block_val.is_nil() {
             let bump = Bump::new();
             let block = crate::style::parse_block(block_val, &bump)?;
-            block.render(area, frame.buffer_mut());
+            draw_stats::timed(|| block.render(area, frame.buffer_mut()));
         }
     }
 
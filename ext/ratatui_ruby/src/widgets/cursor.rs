@@ -1,13 +1,26 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::cursor::{self, CursorStyle};
 use magnus::{prelude::*, Error, Value};
 use ratatui::{layout::Rect, Frame};
 
 pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let x: u16 = node.funcall("x", ())?;
     let y: u16 = node.funcall("y", ())?;
-    frame.set_cursor_position((area.x + x, area.y + y));
+    let shape_val: Value = node.funcall("shape", ())?;
+    let blinking: bool = node.funcall("blinking", ())?;
+    let visible: bool = node.funcall("visible", ())?;
+
+    if !shape_val.is_nil() {
+        let shape = cursor::parse_shape(shape_val)?;
+        cursor::request_style(CursorStyle { shape, blinking });
+    }
+    if visible {
+        cursor::set_position(frame, (area.x + x, area.y + y));
+    } else {
+        cursor::request_hidden();
+    }
     Ok(())
 }
 
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use crate::text::parse_span;
 use bumpalo::Bump;
@@ -40,7 +41,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         gauge = gauge.block(parse_block(block_val, &bump)?);
     }
 
-    frame.render_widget(gauge, area);
+    draw_stats::render_widget(frame, gauge, area);
     Ok(())
 }
 
@@ -70,9 +70,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
             let index = isize::try_from(i)
                 .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
             let child: Value = children_array.entry(index)?;
-            if let Err(e) = render_node(frame, chunks[i], child) {
-                eprintln!("Error rendering child {i}: {e:?}");
-            }
+            render_node(frame, chunks[i], child)?;
         }
     }
     Ok(())
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use crate::text::{parse_line, parse_span};
 use crate::widgets::list_state::RubyListState;
@@ -106,7 +107,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         list = list.block(parse_block(block_val, &bump)?);
     }
 
-    frame.render_stateful_widget(list, area, &mut state);
+    draw_stats::render_stateful_widget(frame, list, area, &mut state);
     Ok(())
 }
 
@@ -209,7 +210,7 @@ pub fn render_stateful(
     // Borrow the inner ListState, render, and release the borrow immediately
     {
         let mut inner_state = state.borrow_mut();
-        frame.render_stateful_widget(list, area, &mut inner_state);
+        draw_stats::render_stateful_widget(frame, list, area, &mut inner_state);
     }
     // Borrow is now released
 
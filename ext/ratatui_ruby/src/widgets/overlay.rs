@@ -15,9 +15,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         let index = isize::try_from(i)
             .map_err(|e| Error::new(ruby.exception_range_error(), e.to_string()))?;
         let layer: Value = layers_array.entry(index)?;
-        if let Err(e) = render_node(frame, area, layer) {
-            eprintln!("Error rendering overlay layer {i}: {e:?}");
-        }
+        render_node(frame, area, layer)?;
     }
     Ok(())
 }
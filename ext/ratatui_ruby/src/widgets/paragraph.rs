@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Symbol, Value};
@@ -53,7 +54,7 @@ fn create_paragraph(node: Value, bump: &Bump) -> Result<Paragraph<'_>, Error> {
 pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let bump = Bump::new();
     let paragraph = create_paragraph(node, &bump)?;
-    frame.render_widget(paragraph, area);
+    draw_stats::render_widget(frame, paragraph, area);
     Ok(())
 }
 
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use magnus::Value;
 use ratatui::{
     layout::Rect,
@@ -13,7 +14,7 @@ pub fn render(frame: &mut Frame, area: Rect, _node: Value) {
     // RatatuiLogo does not support custom styling (it has fixed colors).
     // It requires a size argument.
     let widget = RatatuiLogo::new(RatatuiLogoSize::Small);
-    frame.render_widget(widget, area);
+    draw_stats::render_widget(frame, widget, area);
 }
 
 #[cfg(test)]
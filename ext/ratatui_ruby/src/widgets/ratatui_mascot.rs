@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::parse_block;
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Value};
@@ -16,11 +17,11 @@ pub fn render_ratatui_mascot(frame: &mut Frame, area: Rect, node: Value) -> Resu
         let bump = Bump::new();
         let block = parse_block(block_val, &bump)?;
         inner_area = block.inner(area);
-        frame.render_widget(block, area);
+        draw_stats::render_widget(frame, block, area);
     }
 
     let widget = RatatuiMascot::new();
-    frame.render_widget(widget, inner_area);
+    draw_stats::render_widget(frame, widget, inner_area);
     Ok(())
 }
 
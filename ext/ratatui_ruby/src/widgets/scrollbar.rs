@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::parse_block;
 use crate::widgets::scrollbar_state::RubyScrollbarState;
 use bumpalo::Bump;
@@ -79,13 +80,13 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     }
 
     if block_val.is_nil() {
-        frame.render_stateful_widget(scrollbar, area, &mut state);
+        draw_stats::render_stateful_widget(frame, scrollbar, area, &mut state);
     } else {
         let bump = Bump::new();
         let block = parse_block(block_val, &bump)?;
         let inner_area = block.inner(area);
-        frame.render_widget(block, area);
-        frame.render_stateful_widget(scrollbar, inner_area, &mut state);
+        draw_stats::render_widget(frame, block, area);
+        draw_stats::render_stateful_widget(frame, scrollbar, inner_area, &mut state);
     }
     Ok(())
 }
@@ -168,13 +169,13 @@ pub fn render_stateful(
     {
         let mut inner_state = state.borrow_mut();
         if block_val.is_nil() {
-            frame.render_stateful_widget(scrollbar, area, &mut inner_state);
+            draw_stats::render_stateful_widget(frame, scrollbar, area, &mut inner_state);
         } else {
             let bump = Bump::new();
             let block = parse_block(block_val, &bump)?;
             let inner_area = block.inner(area);
-            frame.render_widget(block, area);
-            frame.render_stateful_widget(scrollbar, inner_area, &mut inner_state);
+            draw_stats::render_widget(frame, block, area);
+            draw_stats::render_stateful_widget(frame, scrollbar, inner_area, &mut inner_state);
         }
     }
 
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_bar_set, parse_block, parse_style};
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, RString, Value};
@@ -72,7 +73,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         sparkline = sparkline.bar_set(parse_bar_set(bar_set_val, &bump)?);
     }
 
-    frame.render_widget(sparkline, area);
+    draw_stats::render_widget(frame, sparkline, area);
     Ok(())
 }
 
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::{parse_block, parse_style};
 use crate::text::{parse_line, parse_span};
 use crate::widgets::table_state::RubyTableState;
@@ -118,7 +119,7 @@ pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
         *state.offset_mut() = offset;
     }
 
-    frame.render_stateful_widget(table, area, &mut state);
+    draw_stats::render_stateful_widget(frame, table, area, &mut state);
     Ok(())
 }
 
@@ -221,7 +222,7 @@ pub fn render_stateful(
     // Borrow the inner TableState, render, and release the borrow immediately
     {
         let mut inner_state = state.borrow_mut();
-        frame.render_stateful_widget(table, area, &mut inner_state);
+        draw_stats::render_stateful_widget(frame, table, area, &mut inner_state);
     }
 
     Ok(())
@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::draw_stats;
 use crate::style::parse_block;
 use bumpalo::Bump;
 use magnus::{prelude::*, Error, Value};
@@ -9,7 +10,7 @@ use ratatui::{layout::Rect, text::Line, widgets::Tabs, Frame};
 pub fn render(frame: &mut Frame, area: Rect, node: Value) -> Result<(), Error> {
     let bump = Bump::new();
     let tabs = create_tabs(node, &bump)?;
-    frame.render_widget(tabs, area);
+    draw_stats::render_widget(frame, tabs, area);
     Ok(())
 }
 
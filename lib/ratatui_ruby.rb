@@ -12,6 +12,10 @@ require_relative "ratatui_ruby/widgets"  # Widgets::Block, Widgets::Paragraph, e
 require_relative "ratatui_ruby/buffer"   # Buffer::Cell (for inspection)
 require_relative "ratatui_ruby/schema/text"  # Text::Span, Text::Line
 require_relative "ratatui_ruby/schema/draw"  # Draw commands
+require_relative "ratatui_ruby/viewport"     # Viewport (fullscreen, inline, fixed)
+require_relative "ratatui_ruby/terminal_colors" # TerminalColors (query_terminal_colors)
+require_relative "ratatui_ruby/draw_stats"   # DrawStats (returned by draw)
+require_relative "ratatui_ruby/terminal"     # Terminal (headless terminals and the default one)
 
 # Event types
 require_relative "ratatui_ruby/event"
@@ -47,16 +51,185 @@ module RatatuiRuby
 
     # Raised when an API safety contract is violated (e.g., accessing a Frame outside its valid scope).
     class Safety < Error; end
+
+    # Raised when the native extension panics while drawing.
+    #
+    # This is a bug in RatatuiRuby. The terminal stays usable, so the app can restore it.
+    class Panic < Error; end
   end
 
   ##
   # Initializes the terminal for TUI mode.
-  # Enters alternate screen and enables raw mode.
+  # Enables raw mode. Full-screen viewports also enter the alternate screen.
   #
+  # [mouse] which mouse events to capture (default: true). <tt>:all</tt> (or +true+) reports
+  #         clicks, drags, and movement. <tt>:clicks</tt> reports only buttons and the wheel.
+  #         +false+ leaves the mouse to the terminal, so users can select text.
   # [focus_events] whether to enable focus gain/loss events (default: true).
   # [bracketed_paste] whether to enable bracketed paste mode (default: true).
-  def self.init_terminal(focus_events: true, bracketed_paste: true)
-    _init_terminal(focus_events, bracketed_paste)
+  # [viewport] a {Viewport} selecting where to draw (default: full screen).
+  #            Inline and fixed viewports draw on the main screen.
+  # [keyboard_enhancement] whether to enable the Kitty keyboard protocol (default: false).
+  #                        +true+ enables every flag. An Array picks individual flags from
+  #                        {KEYBOARD_ENHANCEMENT_FLAGS}. Terminals without the protocol ignore it.
+  # [color_depth] how many colors the terminal shows (default: <tt>:auto</tt>). <tt>:auto</tt>
+  #               detects it from <tt>NO_COLOR</tt>, <tt>COLORTERM</tt>, and <tt>TERM</tt>.
+  #               <tt>:truecolor</tt>, <tt>:ansi256</tt>, <tt>:ansi16</tt>, or <tt>:none</tt>
+  #               override it. Colors the terminal lacks are drawn as the nearest one it has,
+  #               and <tt>:none</tt> draws no colors at all.
+  # [synchronized_output] whether to wrap each frame in a synchronized update (default: true).
+  #                       Supporting terminals show each frame at once instead of painting it
+  #                       as it arrives, which stops large redraws from tearing. Others ignore it.
+  # [output] where to draw (default: <tt>:stdout</tt>). <tt>:tty</tt> draws on the controlling
+  #          terminal, so stdout stays free for the app's result even when it is piped.
+  #          An IO or an Integer file descriptor draws there. Input is read from the
+  #          controlling terminal whenever stdin is not one. Inline viewports need
+  #          <tt>:stdout</tt>, because they ask stdout for the cursor position.
+  #
+  # === Examples
+  #
+  #   # Draw a 3-line live region below the shell prompt
+  #   RatatuiRuby.init_terminal(viewport: RatatuiRuby::Viewport.inline(3))
+  #
+  #   # Receive key repeat and release events in Kitty, WezTerm, or Foot
+  #   RatatuiRuby.init_terminal(keyboard_enhancement: true)
+  #
+  #   # Only tell Esc apart from Alt sequences
+  #   RatatuiRuby.init_terminal(keyboard_enhancement: [:disambiguate_escape_codes])
+  #
+  #   # Keep native text selection working
+  #   RatatuiRuby.init_terminal(mouse: false)
+  #
+  #   # tmux without the RGB feature, where COLORTERM leaks in from the outer terminal
+  #   RatatuiRuby.init_terminal(color_depth: :ansi256)
+  #
+  #   # A picker used as `vim $(pick)`: draw on the terminal, print the choice to stdout
+  #   RatatuiRuby.init_terminal(output: :tty)
+  def self.init_terminal(mouse: true, focus_events: true, bracketed_paste: true, viewport: nil, keyboard_enhancement: false, color_depth: :auto, synchronized_output: true, output: :stdout)
+    if output.respond_to?(:fileno)
+      output.flush
+      output = output.fileno
+    end
+    _init_terminal(mouse, focus_events, bracketed_paste, Viewport.resolve(viewport), keyboard_enhancement, color_depth, synchronized_output, output)
+    restore_terminal_at_exit
+  end
+
+  # Restores the terminal when the process exits, even if the app forgot to.
+  # Covers +exit+, uncaught exceptions, and signals Ruby turns into exceptions (SIGTERM, SIGHUP).
+  def self.restore_terminal_at_exit
+    return if @restore_terminal_at_exit
+
+    at_exit { restore_terminal }
+    @restore_terminal_at_exit = true
+  end
+  private_class_method :restore_terminal_at_exit
+
+  # Flags accepted by the <tt>keyboard_enhancement:</tt> option of {init_terminal}.
+  #
+  # [:disambiguate_escape_codes] Report Esc, Alt+key, and Ctrl+key unambiguously.
+  # [:report_event_types] Report key repeats and releases, not just presses.
+  # [:report_alternate_keys] Report the shifted key instead of Shift plus the base key.
+  # [:report_all_keys_as_escape_codes] Report every key, including lone modifier keys, as escape codes.
+  KEYBOARD_ENHANCEMENT_FLAGS = %i[
+    disambiguate_escape_codes
+    report_event_types
+    report_alternate_keys
+    report_all_keys_as_escape_codes
+  ].freeze
+
+  ##
+  # Starts capturing mouse events.
+  #
+  # Mouse capture takes over the terminal's own text selection. Apps that want both
+  # can capture the mouse only while it is needed, such as while a menu is open.
+  #
+  # [mode] <tt>:all</tt> (clicks, drags, and movement) or <tt>:clicks</tt> (buttons and the wheel).
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  #
+  # === Example
+  #
+  #   RatatuiRuby.enable_mouse_capture(:clicks)
+  def self.enable_mouse_capture(mode = :all)
+    _set_mouse_capture(mode)
+  end
+
+  ##
+  # Stops capturing mouse events and hands the mouse back to the terminal.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  def self.disable_mouse_capture
+    _set_mouse_capture(false)
+  end
+
+  ##
+  # Returns the current mouse capture mode: <tt>:all</tt>, <tt>:clicks</tt>, or +false+.
+  def self.mouse_capture
+    _input_modes.fetch(:mouse, false)
+  end
+
+  ##
+  # Starts reporting Event::FocusGained and Event::FocusLost.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  def self.enable_focus_events
+    _set_focus_events(true)
+  end
+
+  ##
+  # Stops reporting focus changes.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  def self.disable_focus_events
+    _set_focus_events(false)
+  end
+
+  ##
+  # Whether focus changes are reported.
+  def self.focus_events?
+    _input_modes.fetch(:focus_events, false)
+  end
+
+  ##
+  # Starts bracketed paste mode. Pasted text arrives as a single Event::Paste.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  def self.enable_bracketed_paste
+    _set_bracketed_paste(true)
+  end
+
+  ##
+  # Stops bracketed paste mode. Pasted text arrives as individual key events.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  def self.disable_bracketed_paste
+    _set_bracketed_paste(false)
+  end
+
+  ##
+  # Whether bracketed paste mode is on.
+  def self.bracketed_paste?
+    _input_modes.fetch(:bracketed_paste, false)
+  end
+
+  # (Native methods _set_mouse_capture, _set_focus_events, _set_bracketed_paste,
+  # and _input_modes implemented in Rust)
+  private_class_method :_set_mouse_capture, :_set_focus_events, :_set_bracketed_paste, :_input_modes
+
+  ##
+  # Initializes a headless test terminal of the given size.
+  #
+  # Nothing is written to the real terminal. Use <tt>get_buffer_content</tt>
+  # and <tt>get_cell_at</tt> to inspect what was drawn. Mouse capture, focus events,
+  # and bracketed paste start off, and the toggles above only record the change.
+  #
+  # [width] Integer number of columns.
+  # [height] Integer number of rows.
+  # [viewport] a {Viewport} selecting where to draw (default: full screen).
+  # [color_depth] the color depth to simulate (default: <tt>:truecolor</tt>). See {init_terminal}.
+  #               <tt>get_cell_at</tt> reports colors as the terminal would receive them.
+  def self.init_test_terminal(width, height, viewport: nil, color_depth: :truecolor)
+    _init_test_terminal(width, height, Viewport.resolve(viewport), color_depth)
   end
 
   @experimental_warnings = true
@@ -71,6 +244,35 @@ module RatatuiRuby
   # :singleton-method: restore_terminal
   # Restores the terminal to its original state.
   # Leaves alternate screen and disables raw mode.
+  # Runs automatically at exit, on Rust panics, and on crashes such as SIGSEGV.
+  # Inline and fixed viewports keep their last frame on screen, and the cursor
+  # moves to the line below them. A cursor shape set with Frame#set_cursor_style
+  # goes back to the user's own.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: color_depth
+  # Returns the color depth <tt>draw</tt> renders for: <tt>:truecolor</tt>, <tt>:ansi256</tt>,
+  # <tt>:ansi16</tt>, or <tt>:none</tt>. Without a terminal, colors are left as they are
+  # and this returns <tt>:truecolor</tt>.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: get_cursor_style
+  # Returns the cursor state after the last <tt>draw</tt> as a Hash with
+  # <tt>:shape</tt>, <tt>:blinking</tt>, and <tt>:visible</tt> keys.
+  #
+  #   RatatuiRuby.get_cursor_style # => { shape: :bar, blinking: false, visible: true }
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: get_buffer
+  # Returns a copy of the test terminal's screen as a {Buffer::Buffer}.
+  #
+  #   File.write("screenshot.html", RatatuiRuby.get_buffer.to_html)
   #
   # (Native method implemented in Rust)
 
@@ -103,6 +305,9 @@ module RatatuiRuby
   # (Native method _init_terminal implemented in Rust)
   private_class_method :_init_terminal
 
+  # (Native method _init_test_terminal implemented in Rust)
+  private_class_method :_init_test_terminal
+
   ##
   # Draws the given UI node tree to the terminal.
   #
@@ -117,6 +322,8 @@ module RatatuiRuby
   # [tree] A widget tree (Widgets::Paragraph, Layout::Layout, etc.) to render. Optional if
   #        a block is given.
   #
+  # Returns the frame's {DrawStats}, or +nil+ if the terminal is not initialized.
+  #
   # === Examples
   #
   # Legacy declarative style (tree-based):
@@ -129,6 +336,11 @@ module RatatuiRuby
   #     frame.render_widget(Widgets::Paragraph.new(text: "Hello"), frame.area)
   #   end
   #
+  # Performance overlay:
+  #
+  #   stats = RatatuiRuby.draw(view)
+  #   status = format("%.1f ms, %d cells", stats.duration * 1000, stats.cells_changed)
+  #
   def self.draw(tree = nil, &block)
     if tree && block
       raise ArgumentError, "Cannot provide both a tree and a block to draw"
@@ -137,16 +349,216 @@ module RatatuiRuby
       raise ArgumentError, "Must provide either a tree or a block to draw"
     end
 
-    if tree
+    stats = if tree
       _draw(tree)
     else
       _draw(&block)
     end
+    stats && DrawStats.new(**stats)
   end
 
   # (Native method _draw implemented in Rust)
   private_class_method :_draw
 
+  ##
+  # Returns the {DrawStats} of the last <tt>draw</tt>, or +nil+ if nothing was drawn
+  # since the terminal was initialized.
+  def self.last_draw_stats
+    stats = _last_draw_stats
+    stats && DrawStats.new(**stats)
+  end
+
+  # (Native method _last_draw_stats implemented in Rust)
+  private_class_method :_last_draw_stats
+
+  ##
+  # Prints permanent lines above an inline viewport.
+  #
+  # CLI tools show a live status area while they work. Their log output must
+  # scroll by above it and stay in scrollback, not get painted over by the
+  # next frame.
+  #
+  # This method inserts +height+ lines directly above the viewport, pushing
+  # older output up. Pass a widget tree, or a block that receives a {Frame}
+  # exactly as large as the inserted lines.
+  #
+  # The viewport is cleared afterwards. Call <tt>draw</tt> to repaint it.
+  # Has no effect unless the terminal uses an inline {Viewport}.
+  #
+  # [height] Integer number of lines to insert.
+  # [tree] A widget tree to render into the lines. Optional if a block is given.
+  #
+  # === Examples
+  #
+  #   RatatuiRuby.insert_before(1, Widgets::Paragraph.new(text: "Compiled app.rb"))
+  #
+  #   RatatuiRuby.insert_before(1) do |frame|
+  #     line = Text::Line.new(spans: [Text::Span.new(content: "done", style: Style::Style.new(fg: :green))])
+  #     frame.render_widget(Widgets::Paragraph.new(text: line), frame.area)
+  #   end
+  def self.insert_before(height, tree = nil, &block)
+    if tree && block
+      raise ArgumentError, "Cannot provide both a tree and a block to insert_before"
+    end
+    unless tree || block
+      raise ArgumentError, "Must provide either a tree or a block to insert_before"
+    end
+
+    if tree
+      _insert_before(height, tree)
+    else
+      _insert_before(height, &block)
+    end
+  end
+
+  # (Native method _insert_before implemented in Rust)
+  private_class_method :_insert_before
+
+  ##
+  # Hands the terminal back to the shell for a while.
+  #
+  # Some tasks need the real terminal: editing a file in <tt>$EDITOR</tt>, running a pager,
+  # or stopping the app with Ctrl-Z. Calling <tt>restore_terminal</tt> and <tt>init_terminal</tt>
+  # by hand forgets the options the app started with.
+  #
+  # This method leaves TUI mode, runs the block, then re-enters TUI mode with the same
+  # options. The next <tt>draw</tt> repaints the whole screen. Returns the block's value.
+  #
+  # Without a block, it stops the process with <tt>SIGTSTP</tt>, as Ctrl-Z does in a
+  # shell. Raw mode swallows Ctrl-Z as a key event, so bind it yourself. The app
+  # resumes when the shell sends <tt>SIGCONT</tt> (<tt>fg</tt>). Job control is only
+  # available on Unix-like platforms.
+  #
+  # Has no effect on the screen of a test terminal, apart from the full repaint.
+  #
+  # === Examples
+  #
+  #   # Edit a file, then come back
+  #   RatatuiRuby.suspend { system(ENV.fetch("EDITOR", "vi"), path) }
+  #
+  #   # Ctrl-Z sends the app to the background
+  #   RatatuiRuby.suspend if event.ctrl_z?
+  def self.suspend
+    _suspend_terminal
+    begin
+      if block_given?
+        yield
+      else
+        Process.kill(:TSTP, 0)
+      end
+    ensure
+      _resume_terminal
+    end
+  end
+
+  # (Native method _suspend_terminal implemented in Rust)
+  private_class_method :_suspend_terminal
+
+  # (Native method _resume_terminal implemented in Rust)
+  private_class_method :_resume_terminal
+
+  ##
+  # :singleton-method: set_title
+  # :call-seq: set_title(title) -> nil
+  #
+  # Sets the terminal window (or tab) title.
+  #
+  # Users juggle many terminal tabs. A title that names the current view or file
+  # helps them find the right one.
+  #
+  # The first call saves the user's own title on the terminal's title stack.
+  # <tt>restore_title</tt> and <tt>restore_terminal</tt> put it back. Terminals
+  # without a title stack keep the last title set.
+  #
+  # [title] String.
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  #
+  #   RatatuiRuby.set_title("notes.md - Editor")
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: restore_title
+  # :call-seq: restore_title() -> nil
+  #
+  # Puts back the title the terminal had before the first <tt>set_title</tt>.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # Copies text to the system clipboard.
+  #
+  # Apps running over SSH cannot reach the local clipboard with tools like <tt>pbcopy</tt>.
+  #
+  # This method asks the terminal to do it with an OSC 52 escape sequence, which works
+  # across SSH. Support varies: most modern terminals accept it, some ask the user first,
+  # and tmux needs <tt>set -g set-clipboard on</tt>. Nothing reports whether it worked.
+  #
+  # [text] String to copy.
+  # [selection] <tt>:clipboard</tt> (default) or <tt>:primary</tt> (the X11 middle-click selection).
+  #
+  # Raises Error::Terminal if the terminal is not initialized.
+  #
+  # === Example
+  #
+  #   RatatuiRuby.copy_to_clipboard(selected_line) if event.ctrl_c?
+  def self.copy_to_clipboard(text, selection: :clipboard)
+    _copy_to_clipboard(text.to_s, selection)
+  end
+
+  # (Native method _copy_to_clipboard implemented in Rust)
+  private_class_method :_copy_to_clipboard
+
+  ##
+  # Asks the terminal for its default colors and palette entries.
+  #
+  # One theme rarely suits both light and dark terminals. Apps can check the
+  # background at startup and pick their styles to match.
+  #
+  # This method sends OSC 10, 11, and 4 queries and waits up to +timeout+ seconds
+  # for the replies. It works before <tt>init_terminal</tt> or inside TUI mode.
+  # Replies are read directly, so they never reach <tt>poll_event</tt> as key presses,
+  # and keys typed meanwhile are still delivered. Do not call it while another thread
+  # is waiting in <tt>poll_event</tt>.
+  #
+  # Terminals that do not answer a query, Windows consoles, processes without a
+  # controlling terminal, and test terminals report +nil+ for those colors.
+  #
+  # [palette] Array of palette indexes (0–255) to query (default: none).
+  # [timeout] Float seconds to wait for the replies (default: 0.1).
+  #
+  # Returns a {TerminalColors}.
+  #
+  # === Examples
+  #
+  #   colors = RatatuiRuby.query_terminal_colors
+  #   colors.background # => "#1e1e2e"
+  #   colors.dark?      # => true
+  #
+  #   RatatuiRuby.query_terminal_colors(palette: [1, 2]).palette
+  #   # => {1 => "#f38ba8", 2 => "#a6e3a1"}
+  def self.query_terminal_colors(palette: [], timeout: 0.1)
+    colors = _query_terminal_colors(palette.map { |index| Integer(index) }, Float(timeout))
+    TerminalColors.new(**colors)
+  end
+
+  # (Native method _query_terminal_colors implemented in Rust)
+  private_class_method :_query_terminal_colors
+
+  ##
+  # :singleton-method: get_title
+  # Returns the title set with <tt>set_title</tt>, or +nil+ if the user's own title is showing.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: get_clipboard_writes
+  # Returns every String a test terminal was asked to copy, oldest first.
+  # Real terminals do not record them.
+  #
+  # (Native method implemented in Rust)
+
   ##
   # Checks for user input.
   #
@@ -154,6 +566,9 @@ module RatatuiRuby
   #
   # This method checks for an event. It returns the event if one is found. It returns {RatatuiRuby::Event::None} if the timeout expires.
   #
+  # The wait releases the GVL, so other Ruby threads keep running while it blocks.
+  # <tt>Thread#raise</tt> and Ctrl-C (<tt>Interrupt</tt>) interrupt it promptly.
+  #
   # [timeout] Float seconds to wait (default: 0.016).
   #           Pass <tt>nil</tt> to block indefinitely (wait forever).
   #           Pass <tt>0.0</tt> for a non-blocking check.
@@ -172,41 +587,53 @@ module RatatuiRuby
   def self.poll_event(timeout: 0.016)
     raise ArgumentError, "timeout must be non-negative" if timeout && timeout < 0
 
-    raw = _poll_event(timeout)
-    return Event::None.new.freeze if raw.nil?
-
-    case raw[:type]
-    when :key
-      Event::Key.new(
-        code: raw[:code],
-        modifiers: (raw[:modifiers] || []).freeze,
-        kind: raw[:kind] || :standard
-      ).freeze
-    when :mouse
-      Event::Mouse.new(
-        kind: raw[:kind].to_s,
-        x: raw[:x],
-        y: raw[:y],
-        button: raw[:button].to_s,
-        modifiers: (raw[:modifiers] || []).freeze
-      ).freeze
-    when :resize
-      Event::Resize.new(width: raw[:width], height: raw[:height]).freeze
-    when :paste
-      Event::Paste.new(content: raw[:content]).freeze
-    when :focus_gained
-      Event::FocusGained.new.freeze
-    when :focus_lost
-      Event::FocusLost.new.freeze
-    else
-      # Fallback for unknown events, though ideally we cover them all
-      nil
-    end
+    Event.from_native(_poll_event(timeout))
   end
 
   # (Native method _poll_event implemented in Rust)
   private_class_method :_poll_event
 
+  ##
+  # Returns an IO that is readable whenever an event is pending.
+  #
+  # Event loops often wait on many sources at once: sockets, subprocess pipes,
+  # and the keyboard. <tt>poll_event</tt> alone cannot join an <tt>IO.select</tt>
+  # call or a fiber scheduler, because it has no file descriptor.
+  #
+  # This IO becomes readable when <tt>poll_event</tt> would return an event
+  # immediately: terminal input arrived, or a test injected an event. Call
+  # <tt>poll_event(timeout: 0.0)</tt> when it is ready. Never read from it
+  # yourself.
+  #
+  # Available on Unix-like platforms. Raises <tt>NotImplementedError</tt> elsewhere.
+  #
+  # === Examples
+  #
+  # With IO.select:
+  #
+  #   loop do
+  #     ready, = IO.select([RatatuiRuby.event_io, socket], nil, nil, 1.0)
+  #     next unless ready
+  #
+  #     handle(RatatuiRuby.poll_event(timeout: 0.0)) if ready.include?(RatatuiRuby.event_io)
+  #     handle_socket(socket.read_nonblock(4096)) if ready.include?(socket)
+  #   end
+  #
+  # With the <tt>async</tt> gem (or any <tt>Fiber.scheduler</tt>):
+  #
+  #   Async do
+  #     loop do
+  #       RatatuiRuby.event_io.wait_readable
+  #       handle(RatatuiRuby.poll_event(timeout: 0.0))
+  #     end
+  #   end
+  def self.event_io
+    @event_io ||= IO.for_fd(_event_io_fd, autoclose: false)
+  end
+
+  # (Native method _event_io_fd implemented in Rust)
+  private_class_method :_event_io_fd
+
   ##
   # Starts the TUI application lifecycle.
   #
@@ -214,14 +641,28 @@ module RatatuiRuby
   #
   # This method handles the safety net. It initializes the terminal, yields a {TUI}, and ensures the terminal state is restored even if exceptions occur.
   #
-  # === Example
+  # === Examples
   #
   #   RatatuiRuby.run(focus_events: false) do |tui|
   #     tui.draw(tui.paragraph(text: "Hi"))
   #     sleep 1
   #   end
-  def self.run(focus_events: true, bracketed_paste: true)
-    init_terminal(focus_events:, bracketed_paste:)
+  #
+  #   # Progress bar below the prompt; the final frame stays in scrollback
+  #   RatatuiRuby.run(viewport: RatatuiRuby::Viewport.inline(1)) do |tui|
+  #     tui.draw(tui.gauge(ratio: 1.0))
+  #   end
+  #
+  #   # Clicks only, so moving the mouse does not wake the event loop
+  #   RatatuiRuby.run(mouse: :clicks) do |tui|
+  #     tui.draw(tui.paragraph(text: "Click me"))
+  #   end
+  #
+  #   # Draw on the terminal and print the result to stdout after restoring it
+  #   choice = RatatuiRuby.run(output: :tty) { |tui| pick(tui) }
+  #   puts choice
+  def self.run(mouse: true, focus_events: true, bracketed_paste: true, viewport: nil, keyboard_enhancement: false, color_depth: :auto, synchronized_output: true, output: :stdout)
+    init_terminal(mouse:, focus_events:, bracketed_paste:, viewport:, keyboard_enhancement:, color_depth:, synchronized_output:, output:)
     yield TUI.new
   ensure
     restore_terminal
@@ -245,18 +686,198 @@ module RatatuiRuby
   #   expect(cell).to be_bold
   #
   def self.get_cell_at(x, y)
-    raw = _get_cell_at(x, y)
-    Buffer::Cell.new(
-      char: raw["char"],
-      fg: raw["fg"],
-      bg: raw["bg"],
-      modifiers: raw["modifiers"] || []
-    )
+    Buffer::Cell.from_native(_get_cell_at(x, y))
   end
 
   # (Native method _get_cell_at implemented in Rust)
   private_class_method :_get_cell_at
 
+  ##
+  # Renders a widget into a new {Buffer::Buffer}, without a terminal.
+  #
+  # <tt>draw</tt> needs a terminal, and tests replace the process-wide one with
+  # <tt>init_test_terminal</tt>. This renders into a buffer of its own instead, so
+  # nothing global changes. Use it to pre-render panels, render on a server, or
+  # test widgets in parallel.
+  #
+  # Pass a widget, or a block that receives a {Frame} like <tt>draw</tt>.
+  #
+  # [widget] the widget tree to render.
+  # [width] Integer number of columns.
+  # [height] Integer number of rows.
+  # [color_depth] the color depth to downsample to (default: <tt>:truecolor</tt>).
+  #               See <tt>init_terminal</tt>.
+  #
+  # === Examples
+  #
+  #   buffer = RatatuiRuby.render_to_buffer(paragraph, width: 20, height: 3)
+  #   buffer.lines.first # => "Hello               "
+  #
+  #   buffer = RatatuiRuby.render_to_buffer(width: 40, height: 10) do |frame|
+  #     left, right = RatatuiRuby::Layout::Layout.split(frame.area, direction: :horizontal, constraints: halves)
+  #     frame.render_widget(menu, left)
+  #     frame.render_widget(preview, right)
+  #   end
+  def self.render_to_buffer(widget = nil, width:, height:, color_depth: :truecolor, &block)
+    if widget && block
+      raise ArgumentError, "Cannot provide both a widget and a block to render_to_buffer"
+    end
+
+    _render_to_buffer(width, height, color_depth, widget, &block)
+  end
+
+  # (Native method _render_to_buffer implemented in Rust)
+  private_class_method :_render_to_buffer
+
+  # Registers how to render instances of +widget_class+ and its subclasses.
+  #
+  # Built-in widgets are rendered natively, and custom widgets render themselves with
+  # a +render+ method. Registering a renderer covers everything else: classes from other
+  # gems that know nothing about RatatuiRuby, modules mixed into several widget classes,
+  # and replacing how a built-in widget draws.
+  #
+  # The renderer receives the widget, its area (a <tt>Layout::Rect</tt>), and the
+  # frame's {Buffer::Buffer}, which it can write to or render other widgets into.
+  #
+  # Widgets are matched by ancestry, nearest first. At each ancestor, a registered
+  # renderer wins over a built-in one, which wins over a +render+ method defined there.
+  #
+  # Gems with native extensions register renderers here too: there is no Rust API for
+  # it, because Ratatui's types cannot be shared between separately compiled extensions.
+  # Their renderer can call into native code and draw through the buffer it is given.
+  #
+  # [widget_class] Class or Module.
+  # [renderer] anything that responds to +call+, or a block.
+  #
+  # === Example
+  #
+  #   RatatuiRuby.register_widget(Money) do |money, area, buffer|
+  #     color = money.negative? ? :red : :green
+  #     buffer.set_string(area.x, area.y, money.format, { fg: color })
+  #   end
+  #
+  #   RatatuiRuby.draw(Money.new(-12_50, "USD"))
+  def self.register_widget(widget_class, renderer = nil, &block)
+    _register_widget(widget_class, renderer_for(widget_class, renderer, block))
+    nil
+  end
+
+  # Registers how to render instances of +widget_class+ with instances of +state_class+
+  # through <tt>Frame#render_stateful_widget</tt>.
+  #
+  # The renderer receives the widget, its area, the frame's {Buffer::Buffer}, and the
+  # state. Both classes are matched by ancestry, as in <tt>register_widget</tt>.
+  #
+  # [widget_class] Class or Module.
+  # [state_class] Class or Module.
+  # [renderer] anything that responds to +call+, or a block.
+  def self.register_stateful_widget(widget_class, state_class, renderer = nil, &block)
+    unless state_class.is_a?(Module)
+      raise ArgumentError, "Expected a Class or Module for the state, got #{state_class.inspect}"
+    end
+
+    _register_stateful_widget(widget_class, state_class, renderer_for(widget_class, renderer, block))
+    nil
+  end
+
+  def self.renderer_for(widget_class, renderer, block) # :nodoc:
+    unless widget_class.is_a?(Module)
+      raise ArgumentError, "Expected a Class or Module for the widget, got #{widget_class.inspect}"
+    end
+    raise ArgumentError, "Cannot provide both a renderer and a block" if renderer && block
+
+    renderer ||= block
+    raise ArgumentError, "Renderer must respond to call" unless renderer.respond_to?(:call)
+
+    renderer
+  end
+  private_class_method :renderer_for
+
+  # (Native methods _register_widget and _register_stateful_widget implemented in Rust)
+  private_class_method :_register_widget, :_register_stateful_widget
+
+  ##
+  # Starts recording the terminal to an asciicast v2 file at +path+.
+  #
+  # Bug reports and demos are easier to follow as recordings. The file plays back with
+  # <tt>asciinema play</tt> and other asciicast players, and it is plain JSON lines, so
+  # CI can diff it.
+  #
+  # On a real terminal, every flushed frame is written with the bytes the terminal
+  # received and the time since recording started. On a test terminal, every
+  # <tt>draw</tt> is written as a full repaint, and time advances 0.1 seconds per event,
+  # so recording the same test twice produces the same file.
+  #
+  # Terminal resizes are always recorded. Key presses and pastes returned by
+  # <tt>poll_event</tt> are recorded as input when +input+ is true.
+  #
+  # Recording stops with <tt>stop_recording</tt> or when the terminal is restored.
+  # Terminal objects other than <tt>Terminal.default</tt> are not recorded.
+  #
+  # [path] String or Pathname of the <tt>.cast</tt> file to write.
+  # [input] whether to record input (default: +false+).
+  # [title] optional title for players to show.
+  #
+  # Raises <tt>Error::Terminal</tt> if no terminal is initialized or a recording is
+  # already running.
+  #
+  # === Example
+  #
+  #   RatatuiRuby.run do |tui|
+  #     RatatuiRuby.start_recording("bug.cast", input: true) if ENV["RECORD"]
+  #     app.run(tui)
+  #   end
+  def self.start_recording(path, input: false, title: nil)
+    _start_recording(path.to_s, input, title)
+  end
+
+  # (Native method _start_recording implemented in Rust)
+  private_class_method :_start_recording
+
+  ##
+  # :singleton-method: stop_recording
+  # Stops recording and closes the file. Does nothing when not recording.
+  #
+  # Raises +IOError+ if writing the file failed.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # :singleton-method: recording?
+  # Whether a recording is running.
+  #
+  # (Native method implemented in Rust)
+
+  ##
+  # Records the block's frames to +path+, then stops recording.
+  #
+  # Takes the same options as <tt>start_recording</tt>.
+  #
+  #   with_test_terminal(40, 10) do
+  #     RatatuiRuby.record("tmp/login.cast") do
+  #       inject_keys("a", "b", :enter)
+  #       app.run
+  #     end
+  #   end
+  def self.record(path, input: false, title: nil)
+    start_recording(path, input:, title:)
+    begin
+      yield
+    ensure
+      stop_recording
+    end
+  end
+
+  # Hide native Buffer::Buffer helpers
+  Buffer::Buffer.__send__(:private, :_content, :_cell_at, :_regions, :_set_string, :_set_line, :_set_cell)
+
   # Hide native Layout._split helper
   Layout::Layout.singleton_class.__send__(:private, :_split)
+
+  # Hide native Frame#_set_cursor_style helper
+  Frame.__send__(:private, :_set_cursor_style)
+
+  # Hide native Terminal helpers
+  Terminal.singleton_class.__send__(:private, :_new, :_default)
+  Terminal.__send__(:private, :_draw, :_last_draw_stats, :_poll_event, :_inject_event, :_buffer_content, :_cell_at, :_cursor_position)
 end
@@ -8,8 +8,12 @@ module RatatuiRuby
   #
   # This module mirrors +ratatui::buffer+ and contains:
   # - {Cell} — Single terminal cell (for inspection)
+  # - {Buffer} — Rendered cells detached from any terminal
+  # - {Region} — Run of identically styled cells
   module Buffer
   end
 end
 
 require_relative "buffer/cell"
+require_relative "buffer/region"
+require_relative "buffer/buffer"
@@ -0,0 +1,196 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  module Buffer
+    # A grid of rendered cells, detached from any terminal.
+    #
+    # Rendering a widget used to mean replacing the terminal with a test terminal and
+    # reading it back. That rules out rendering on a server, caching rendered panels,
+    # or testing widgets from several threads.
+    #
+    # <tt>RatatuiRuby.render_to_buffer</tt> returns one of these instead. Read it cell by
+    # cell, row by row, or as runs of identically styled text.
+    #
+    # === Examples
+    #
+    #   buffer = RatatuiRuby.render_to_buffer(gauge, width: 20, height: 1)
+    #   buffer.lines       # => ["██████▌     50%     "]
+    #   buffer.cell_at(0, 0).fg # => :green
+    #
+    #   buffer.each_region do |region|
+    #     html << %(<span class="#{region.fg}">#{CGI.escapeHTML(region.text)}</span>)
+    #   end
+    #
+    # === Custom widgets
+    #
+    # A custom widget whose +render+ takes two arguments receives the frame's buffer and
+    # writes to it directly, instead of returning Draw commands. It can read what other
+    # widgets drew underneath. Writes outside the buffer are clipped. A +render+ taking
+    # only <tt>*args</tt>, as delegators' methods do, gets the area alone.
+    #
+    # That buffer only works during the +render+ call. Using it afterwards raises
+    # <tt>RatatuiRuby::Error::Safety</tt>.
+    #
+    #   class Highlight
+    #     def render(area, buffer)
+    #       buffer.set_string(area.x, area.y, "Hello", { fg: :red })
+    #       buffer.set_style(area, { modifiers: [:bold] })
+    #     end
+    #   end
+    #
+    # It can also render other widgets, built-in or custom, with +render_widget+.
+    class Buffer
+      ##
+      # :method: width
+      # Number of columns (Integer).
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: height
+      # Number of rows (Integer).
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: to_ansi
+      # Returns the cells as text with ANSI escape sequences, one line per row.
+      #
+      # Each line starts and ends with default colors. Links become OSC 8 sequences.
+      # Print it, or save it and +cat+ it later.
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: to_html
+      # Returns a standalone HTML document showing the cells in a <tt><pre></tt> with
+      # inline styles.
+      #
+      # Colors use xterm's default palette on a black background. Links become anchors.
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: to_svg
+      # Returns an SVG image with one monospace cell per buffer cell.
+      #
+      # Every character sits at its cell's column, so borders and wide characters line
+      # up in any monospace font. Useful for screenshots in documentation and CI.
+      #
+      #   File.write("doc/images/dashboard.svg", buffer.to_svg)
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: area
+      # The cells the buffer covers, as a <tt>Layout::Rect</tt>.
+      #
+      # A frame's buffer may not start at (0, 0), e.g. in an inline viewport.
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: set_style
+      # Applies a style to every cell in +area+ (a <tt>Layout::Rect</tt>), keeping their
+      # text. Only the parts of +style+ that are set change.
+      #
+      #   buffer.set_style(area, { bg: :blue })
+      #
+      # (Native method implemented in Rust)
+
+      ##
+      # :method: render_widget
+      # Renders a widget into +area+ (a <tt>Layout::Rect</tt>), as
+      # <tt>Frame#render_widget</tt> does. Parts of +area+ outside the buffer are cut off.
+      #
+      #   def render(area, buffer)
+      #     body, footer = RatatuiRuby::Layout::Layout.split(area, constraints: [
+      #       RatatuiRuby::Layout::Constraint.fill, RatatuiRuby::Layout::Constraint.length(1),
+      #     ])
+      #     buffer.render_widget(@table, body)
+      #     buffer.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "#{@rows.size} rows"), footer)
+      #   end
+      #
+      # Only the buffer given to a custom widget's +render+ can render widgets. Others
+      # raise NotImplementedError.
+      #
+      # (Native method implemented in Rust)
+
+      # Writes +string+ starting at the given coordinates, in one row.
+      #
+      # Text past the right edge of the buffer is cut off. Returns the coordinates after
+      # the last character written, as <tt>[x, y]</tt>.
+      #
+      # [x] Column (Integer, duck-typed via +to_int+).
+      # [y] Row (Integer, duck-typed via +to_int+).
+      # [string] Text to write (duck-typed via +to_s+).
+      # [style] Style or Hash (optional).
+      def set_string(x, y, string, style = nil)
+        _set_string(Integer(x), Integer(y), string.to_s, style)
+      end
+
+      # Writes a <tt>Text::Line</tt> (or String) starting at the given coordinates, keeping
+      # the style of each span.
+      #
+      # Returns the coordinates after the last character written, as <tt>[x, y]</tt>.
+      #
+      # [x] Column (Integer, duck-typed via +to_int+).
+      # [y] Row (Integer, duck-typed via +to_int+).
+      # [line] <tt>Text::Line</tt> or String.
+      # [max_width] Number of columns to write at most (optional).
+      def set_line(x, y, line, max_width = nil)
+        _set_line(Integer(x), Integer(y), line, max_width && Integer(max_width))
+      end
+
+      # Replaces the cell at the given coordinates with a {Cell}.
+      #
+      # Does nothing if the coordinates are outside the buffer.
+      #
+      #   buffer.set_cell(area.x, area.y, Buffer::Cell.new(char: "█", fg: :green))
+      def set_cell(x, y, cell)
+        _set_cell(Integer(x), Integer(y), cell)
+      end
+
+      # The text of each row, one String per row.
+      def lines
+        _content.split("\n")
+      end
+
+      # The rows joined by newlines.
+      def to_s
+        lines.join("\n")
+      end
+
+      # Returns the {Cell} at the given coordinates.
+      #
+      # Raises <tt>RatatuiRuby::Error::Terminal</tt> if they are outside the buffer.
+      def cell_at(x, y)
+        Cell.from_native(_cell_at(x, y))
+      end
+
+      # Yields each {Region}: the runs of cells in a row that share colors, modifiers,
+      # and hyperlink, left to right and top to bottom.
+      #
+      # Returns an Enumerator without a block.
+      def each_region(&)
+        return enum_for(:each_region) unless block_given?
+
+        _regions.each { |raw| yield Region.new(**raw) }
+        self
+      end
+
+      # Returns every {Region} as an Array.
+      def regions
+        each_region.to_a
+      end
+
+      # :nodoc:
+      def inspect
+        "#<#{self.class} #{width}x#{height}>"
+      end
+    end
+  end
+end
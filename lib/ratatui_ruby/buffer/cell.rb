@@ -39,6 +39,9 @@ module RatatuiRuby
       # The list of active modifiers (e.g., ["bold", "italic"]).
       attr_reader :modifiers
 
+      # The URL of the hyperlink covering the cell (e.g., "https://example.com"), or nil.
+      attr_reader :hyperlink
+
       # Returns an empty cell (space character, no styles).
       #
       # === Example
@@ -76,17 +79,29 @@ module RatatuiRuby
         symbol(char)
       end
 
+      def self.from_native(raw) # :nodoc:
+        new(
+          char: raw["char"],
+          fg: raw["fg"],
+          bg: raw["bg"],
+          modifiers: raw["modifiers"] || [],
+          hyperlink: raw["hyperlink"]
+        )
+      end
+
       # Creates a new Cell.
       #
       # [symbol] String (single character). Aliased as <tt>char:</tt>.
       # [fg] Symbol or String (nullable).
       # [bg] Symbol or String (nullable).
       # [modifiers] Array of Strings.
-      def initialize(symbol: nil, char: nil, fg: nil, bg: nil, modifiers: [])
+      # [hyperlink] String (nullable).
+      def initialize(symbol: nil, char: nil, fg: nil, bg: nil, modifiers: [], hyperlink: nil)
         @symbol = (symbol || char || " ").freeze
         @fg = fg&.freeze
         @bg = bg&.freeze
         @modifiers = modifiers.map(&:freeze).freeze
+        @hyperlink = hyperlink&.freeze
         freeze
       end
 
@@ -141,7 +156,8 @@ module RatatuiRuby
           char == other.char &&
           fg == other.fg &&
           bg == other.bg &&
-          modifiers == other.modifiers
+          modifiers == other.modifiers &&
+          hyperlink == other.hyperlink
       end
 
       # Returns a string representation of the cell.
@@ -150,6 +166,7 @@ module RatatuiRuby
         parts << "fg=#{fg.inspect}" if fg
         parts << "bg=#{bg.inspect}" if bg
         parts << "modifiers=#{modifiers.inspect}" unless modifiers.empty?
+        parts << "hyperlink=#{hyperlink.inspect}" if hyperlink
         "#<#{self.class} #{parts.join(' ')}>"
       end
 
@@ -161,7 +178,7 @@ module RatatuiRuby
       # Support for pattern matching.
       # Supports both <tt>:symbol</tt> and <tt>:char</tt> keys.
       def deconstruct_keys(keys)
-        { symbol:, char: symbol, fg:, bg:, modifiers: }
+        { symbol:, char: symbol, fg:, bg:, modifiers:, hyperlink: }
       end
     end
   end
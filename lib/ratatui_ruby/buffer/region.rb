@@ -0,0 +1,49 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  module Buffer
+    # A run of identically styled cells in one row of a {Buffer}.
+    #
+    # Exporters and renderers that target other media work in runs of text, not cells.
+    # Grouping the cells first saves them from comparing styles cell by cell.
+    #
+    # +text+ is what a terminal shows. Cells hidden behind wide characters add to
+    # +width+ but not to +text+.
+    class Region < Data.define(:x, :y, :width, :text, :fg, :bg, :modifiers, :hyperlink)
+      ##
+      # :attr_reader: x
+      # Column of the first cell (Integer).
+
+      ##
+      # :attr_reader: y
+      # Row (Integer).
+
+      ##
+      # :attr_reader: width
+      # Number of cells covered (Integer).
+
+      ##
+      # :attr_reader: text
+      # The symbols of the cells (String).
+
+      ##
+      # :attr_reader: fg
+      # Foreground color, like {Cell#fg}.
+
+      ##
+      # :attr_reader: bg
+      # Background color, like {Cell#bg}.
+
+      ##
+      # :attr_reader: modifiers
+      # Modifier names, like {Cell#modifiers}.
+
+      ##
+      # :attr_reader: hyperlink
+      # Link target, or +nil+.
+    end
+  end
+end
@@ -0,0 +1,59 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  # Measurements of one frame drawn by <tt>RatatuiRuby.draw</tt>.
+  #
+  # Slow frames make an app feel sluggish, and the cause is not always obvious.
+  # A frame can be slow because the Ruby code building it is slow, because a widget
+  # is expensive to render, or because it repaints far more of the screen than it
+  # needs to.
+  #
+  # This class tells those apart. Show it in a performance overlay, or assert on
+  # it in tests to catch regressions. On test terminals, +cells_changed+ is exact
+  # and +bytes_written+ is always 0.
+  #
+  # === Examples
+  #
+  #   stats = RatatuiRuby.draw(view)
+  #   fps = 1 / stats.duration
+  #
+  #   # In a test: moving the selection must not repaint the whole screen
+  #   with_test_terminal(80, 24) do
+  #     RatatuiRuby.draw(list_at(0))
+  #     assert_operator RatatuiRuby.draw(list_at(1)).cells_changed, :<, 80 * 24
+  #   end
+  class DrawStats < Data.define(:frame_count, :duration, :convert_duration, :render_duration, :output_duration, :cells_changed, :bytes_written)
+    ##
+    # :attr_reader: frame_count
+    # Frames drawn since the terminal was initialized, including this one (Integer).
+
+    ##
+    # :attr_reader: duration
+    # Seconds the whole draw took (Float).
+
+    ##
+    # :attr_reader: convert_duration
+    # Seconds spent running Ruby code and converting widget trees (Float). This includes
+    # the <tt>draw</tt> block and custom widgets written in Ruby.
+
+    ##
+    # :attr_reader: render_duration
+    # Seconds built-in widgets spent rendering into the buffer (Float).
+
+    ##
+    # :attr_reader: output_duration
+    # The rest of +duration+ (Float): diffing the buffer against the screen, writing the
+    # changes, and placing the cursor.
+
+    ##
+    # :attr_reader: cells_changed
+    # Number of cells the diff sent to the terminal (Integer).
+
+    ##
+    # :attr_reader: bytes_written
+    # Bytes written to the terminal, including escape sequences (Integer).
+  end
+end
@@ -58,6 +58,78 @@ module RatatuiRuby
   #     puts "Mouse event"
   #   end
   class Event
+    # Modifier names reported in Key#modifiers and Mouse#modifiers.
+    #
+    # Terminals without the Kitty keyboard protocol only report <tt>"ctrl"</tt>,
+    # <tt>"alt"</tt>, and <tt>"shift"</tt>.
+    MODIFIERS = %w[alt ctrl hyper meta shift super].freeze
+
+    # Builds an event from the Hash the native extension returns, or None for +nil+.
+    def self.from_native(raw) # :nodoc:
+      return None.new.freeze if raw.nil?
+
+      case raw[:type]
+      when :key
+        Key.new(
+          code: raw[:code],
+          modifiers: (raw[:modifiers] || []).freeze,
+          kind: raw[:kind] || :standard,
+          action: raw[:action] || :press,
+          state: (raw[:state] || []).freeze
+        ).freeze
+      when :mouse
+        Mouse.new(
+          kind: raw[:kind].to_s,
+          x: raw[:x],
+          y: raw[:y],
+          button: raw[:button].to_s,
+          modifiers: (raw[:modifiers] || []).freeze
+        ).freeze
+      when :resize
+        Resize.new(width: raw[:width], height: raw[:height]).freeze
+      when :paste
+        Paste.new(content: raw[:content]).freeze
+      when :focus_gained
+        FocusGained.new.freeze
+      when :focus_lost
+        FocusLost.new.freeze
+      else
+        # Fallback for unknown events, though ideally we cover them all
+        nil
+      end
+    end
+
+    # Returns the type and data <tt>RatatuiRuby.inject_test_event</tt> takes for +event+.
+    def self.to_native(event) # :nodoc:
+      case event
+      when Key
+        ["key", {
+          code: event.code,
+          modifiers: event.modifiers,
+          action: event.action.to_s,
+          state: event.state.map(&:to_s),
+        }]
+      when Mouse
+        ["mouse", {
+          kind: event.kind,
+          button: event.button,
+          x: event.x,
+          y: event.y,
+          modifiers: event.modifiers,
+        }]
+      when Resize
+        ["resize", { width: event.width, height: event.height }]
+      when Paste
+        ["paste", { content: event.content }]
+      when FocusGained
+        ["focus_gained", {}]
+      when FocusLost
+        ["focus_lost", {}]
+      else
+        raise ArgumentError, "Unknown event type: #{event.class}"
+      end
+    end
+
     # Returns true if this is a None event.
     def none?
       false
@@ -60,6 +60,17 @@ module RatatuiRuby
     # * Individual modifiers: <tt>:left_shift</tt>, <tt>:right_control</tt>, <tt>:left_super</tt>
     #
     # These keys will not work in Terminal.app, iTerm2, or GNOME Terminal.
+    #
+    # Pass <tt>keyboard_enhancement: true</tt> to <tt>RatatuiRuby.init_terminal</tt> to enable the
+    # protocol. Key repeats and releases then arrive as separate events. Releases never equal
+    # a Symbol or String, but #code and pattern matching still see them, so check #action there:
+    #
+    #   case event
+    #   in type: :key, code: "w", action: :press | :repeat
+    #     move_forward
+    #   in type: :key, code: "w", action: :release
+    #     stop
+    #   end
     class Key < Event
       include Character
       include Media
@@ -72,7 +83,8 @@ module RatatuiRuby
       #   puts event.code # => "enter"
       attr_reader :code
 
-      # List of active modifiers (<tt>"ctrl"</tt>, <tt>"alt"</tt>, <tt>"shift"</tt>).
+      # List of active modifiers (<tt>"ctrl"</tt>, <tt>"alt"</tt>, <tt>"shift"</tt>,
+      # <tt>"super"</tt>, <tt>"hyper"</tt>, <tt>"meta"</tt>). See Event::MODIFIERS.
       #
       #   puts event.modifiers # => ["ctrl", "shift"]
       attr_reader :modifiers
@@ -86,6 +98,24 @@ module RatatuiRuby
       #   event.kind # => :media
       attr_reader :kind
 
+      # What happened to the key.
+      #
+      # One of: <tt>:press</tt>, <tt>:repeat</tt>, <tt>:release</tt>.
+      #
+      # Terminals only report repeats and releases when the Kitty keyboard protocol is
+      # enabled with <tt>keyboard_enhancement:</tt>. Otherwise every event is a press.
+      #
+      #   event.action # => :release
+      attr_reader :action
+
+      # Lock and keypad state reported with the key.
+      #
+      # Any of: <tt>:keypad</tt>, <tt>:caps_lock</tt>, <tt>:num_lock</tt>. Only terminals using the
+      # Kitty keyboard protocol report it.
+      #
+      #   event.state # => [:caps_lock]
+      attr_reader :state
+
       # Returns true for Key events.
       #
       #   event.key?    # => true
@@ -104,10 +134,37 @@ module RatatuiRuby
       # [kind]
       #   The key category (Symbol). One of: <tt>:standard</tt>, <tt>:function</tt>,
       #   <tt>:media</tt>, <tt>:modifier</tt>, <tt>:system</tt>. Defaults to <tt>:standard</tt>.
-      def initialize(code:, modifiers: [], kind: :standard)
+      # [action]
+      #   <tt>:press</tt>, <tt>:repeat</tt>, or <tt>:release</tt> (Symbol). Defaults to <tt>:press</tt>.
+      # [state]
+      #   Lock and keypad state (Array<Symbol>). Defaults to <tt>[]</tt>.
+      def initialize(code:, modifiers: [], kind: :standard, action: :press, state: [])
         @code = code.freeze
         @modifiers = modifiers.map(&:freeze).sort.freeze
         @kind = kind
+        @action = action
+        @state = state.freeze
+      end
+
+      # Returns true if the key went down.
+      #
+      #   event.press? # => true
+      def press?
+        @action == :press
+      end
+
+      # Returns true if the key is held down and auto-repeating.
+      #
+      #   event.repeat? # => false
+      def repeat?
+        @action == :repeat
+      end
+
+      # Returns true if the key was let go.
+      #
+      #   event.release? # => false
+      def release?
+        @action == :release
       end
 
       # Compares the event with another object.
@@ -116,12 +173,16 @@ module RatatuiRuby
       # - If +other+ is a +String+, compares against #to_s.
       # - If +other+ is a +Key+, compares as a value object.
       # - Otherwise, compares using standard equality.
+      #
+      # Releases never equal a Symbol or String, so <tt>event == :q</tt> fires once per
+      # keypress even when the Kitty keyboard protocol reports releases. Repeats do match.
+      # Check #release? or compare #to_sym to handle releases.
       def ==(other)
         case other
         when Symbol
-          to_sym == other
+          !release? && to_sym == other
         when String
-          to_s == other
+          !release? && to_s == other
         when Key
           code == other.code && modifiers == other.modifiers
         else
@@ -131,8 +192,8 @@ module RatatuiRuby
 
       # Converts the event to a Symbol representation.
       #
-      # The format is <tt>[modifiers_]code</tt>. Modifiers are sorted alphabetically (alt, ctrl, shift)
-      # and joined by underscores.
+      # The format is <tt>[modifiers_]code</tt>. Modifiers are sorted alphabetically
+      # (alt, ctrl, hyper, meta, shift, super) and joined by underscores.
       #
       # === Supported Keys
       #
@@ -165,6 +226,7 @@ module RatatuiRuby
       # * <tt>:alt_enter</tt>
       # * <tt>:shift_left</tt>
       # * <tt>:ctrl_alt_delete</tt>
+      # * <tt>:ctrl_super_s</tt>
       def to_sym
         mods = @modifiers.join("_")
         if mods.empty?
@@ -193,7 +255,10 @@ module RatatuiRuby
 
       # Returns inspection string.
       def inspect
-        "#<#{self.class} code=#{@code.inspect} modifiers=#{@modifiers.inspect} kind=#{@kind.inspect}>"
+        details = "code=#{@code.inspect} modifiers=#{@modifiers.inspect} kind=#{@kind.inspect}"
+        details += " action=#{@action.inspect}" unless press?
+        details += " state=#{@state.inspect}" unless @state.empty?
+        "#<#{self.class} #{details}>"
       end
 
       # Supports dynamic key predicate methods via method_missing.
@@ -224,8 +289,12 @@ module RatatuiRuby
       #
       #   event.media_pause?  # => true ONLY for media pause
       #   event.code == "pause"  # => true ONLY for system pause
+      #
+      # Like #==, key predicates are false for releases.
       def method_missing(name, *args, &block)
         if name.to_s.end_with?("?")
+          return false if release?
+
           key_name = name.to_s[0...-1]
           key_sym = key_name.to_sym
 
@@ -262,9 +331,11 @@ module RatatuiRuby
       #     puts "Ctrl+C pressed"
       #   in type: :key, kind: :media
       #     puts "Media key pressed"
+      #   in type: :key, action: :release
+      #     puts "Key released"
       #   end
       def deconstruct_keys(keys)
-        { type: :key, code: @code, modifiers: @modifiers, kind: @kind }
+        { type: :key, code: @code, modifiers: @modifiers, kind: @kind, action: @action, state: @state }
       end
     end
   end
@@ -40,7 +40,7 @@ module RatatuiRuby
       #
       # Can be <tt>nil</tt>, which is treated as <tt>"none"</tt>.
       attr_reader :button
-      # List of active modifiers.
+      # List of active modifiers. See Event::MODIFIERS.
       #
       #   puts event.modifiers # => ["ctrl"]
       attr_reader :modifiers
@@ -179,5 +179,48 @@ module RatatuiRuby
     # - RatatuiRuby::Cursor (Tree API alternative)
     #
     # (Native method implemented in Rust)
+
+    ##
+    # Sets the cursor shape.
+    #
+    # Modal editors tell users which mode they are in by the cursor: a block in
+    # normal mode, a bar in insert mode. Terminals default to whatever the user
+    # configured.
+    #
+    # This method changes the shape for this frame and every later one, until
+    # changed again. <tt>restore_terminal</tt> and <tt>RatatuiRuby.suspend</tt>
+    # put the user's own shape back.
+    #
+    # [shape]
+    #   <tt>:block</tt>, <tt>:underline</tt>, <tt>:bar</tt>, or <tt>:default</tt>
+    #   for the user's own shape.
+    # [blinking]
+    #   Whether the cursor blinks (default: <tt>false</tt>).
+    #
+    # === Example
+    #
+    #   RatatuiRuby.draw do |frame|
+    #     frame.render_widget(editor, frame.area)
+    #     frame.set_cursor_style(@mode == :insert ? :bar : :block)
+    #     frame.set_cursor_position(@cursor_x, @cursor_y)
+    #   end
+    def set_cursor_style(shape, blinking: false)
+      _set_cursor_style(shape, blinking)
+    end
+
+    # (Native method _set_cursor_style implemented in Rust, made private in ratatui_ruby.rb)
+
+    ##
+    # :method: hide_cursor
+    # :call-seq: hide_cursor() -> nil
+    #
+    # Hides the cursor for this frame.
+    #
+    # The cursor is shown only in frames that set its position. A component that
+    # sets the position cannot know whether a dialog drawn over it has focus.
+    #
+    # This method hides the cursor even if something in this frame set its position.
+    #
+    # (Native method implemented in Rust)
   end
 end
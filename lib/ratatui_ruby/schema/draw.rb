@@ -19,6 +19,12 @@ module RatatuiRuby
   #       ]
   #     end
   #   end
+  #
+  # Commands run in order, so later ones draw over earlier ones. Line, fill, style,
+  # box, and clear commands are clipped to the widget's area.
+  #
+  # Widgets whose +render+ takes a second argument get a Buffer::Buffer to write to
+  # instead. See Buffer::Buffer.
   module Draw
     # Command to draw a string at the given coordinates.
     #
@@ -35,6 +41,53 @@ module RatatuiRuby
     # [cell] The Cell to draw.
     CellCmd = Data.define(:x, :y, :cell)
 
+    # Command to render a widget into an area.
+    #
+    # The widget can be any built-in or custom widget. Parts of +area+ outside the
+    # frame are cut off.
+    #
+    # [widget] The widget to render.
+    # [area] The Layout::Rect to render into (absolute).
+    WidgetCmd = Data.define(:widget, :area)
+
+    # Command to draw a styled line at the given coordinates.
+    #
+    # [x] X coordinate (absolute).
+    # [y] Y coordinate (absolute).
+    # [line] The Text::Line (or String) to draw. Each span keeps its style.
+    # [max_width] Number of columns to draw at most, or nil.
+    LineCmd = Data.define(:x, :y, :line, :max_width)
+
+    # Command to fill an area with one symbol and style, replacing what was there.
+    #
+    # [area] The Layout::Rect to fill (absolute).
+    # [symbol] The String every cell shows.
+    # [style] Style hash or Style object.
+    FillCmd = Data.define(:area, :symbol, :style)
+
+    # Command to patch the style of an area, keeping its text.
+    #
+    # Only the parts of +style+ that are set change.
+    #
+    # [area] The Layout::Rect to restyle (absolute).
+    # [style] Style hash or Style object.
+    StyleCmd = Data.define(:area, :style)
+
+    # Command to draw a border around an area, leaving the inside alone.
+    #
+    # [area] The Layout::Rect the border goes around the edge of (absolute).
+    # [border_type] <tt>:plain</tt>, <tt>:rounded</tt>, <tt>:double</tt>, <tt>:thick</tt>,
+    #   <tt>:quadrant_inside</tt>, or <tt>:quadrant_outside</tt>.
+    # [border_set] Hash of border characters, as Widgets::Block takes, or nil.
+    #   Overrides +border_type+.
+    # [style] Style of the border (Hash or Style).
+    BoxCmd = Data.define(:area, :border_type, :border_set, :style)
+
+    # Command to reset an area to empty cells, as Widgets::Clear does.
+    #
+    # [area] The Layout::Rect to clear (absolute).
+    ClearCmd = Data.define(:area)
+
     # Creates a string draw command.
     #
     # [x] X coordinate (Integer, duck-typed via +to_int+).
@@ -49,5 +102,70 @@ module RatatuiRuby
     # [y] Y coordinate (Integer, duck-typed via +to_int+).
     # [cell] Cell to draw.
     def self.cell(x, y, cell) = CellCmd.new(x: Integer(x), y: Integer(y), cell:)
+
+    # Creates a widget draw command.
+    #
+    # Lets custom widgets compose built-in ones:
+    #
+    #   class TitledTable
+    #     def render(area)
+    #       table_area, footer_area = RatatuiRuby::Layout::Layout.split(
+    #         area, constraints: [RatatuiRuby::Layout::Constraint.fill, RatatuiRuby::Layout::Constraint.length(1)]
+    #       )
+    #       [
+    #         RatatuiRuby::Draw.widget(@table, table_area),
+    #         RatatuiRuby::Draw.widget(RatatuiRuby::Widgets::Paragraph.new(text: "#{@rows.size} rows"), footer_area),
+    #       ]
+    #     end
+    #   end
+    #
+    # [widget] Widget to render.
+    # [area] Layout::Rect to render into.
+    def self.widget(widget, area) = WidgetCmd.new(widget:, area:)
+
+    # Creates a line draw command.
+    #
+    #   RatatuiRuby::Draw.line(area.x, area.y, RatatuiRuby::Text::Line.new(spans: [
+    #     RatatuiRuby::Text::Span.styled("12:04 ", RatatuiRuby::Style::Style.new(fg: :dark_gray)),
+    #     RatatuiRuby::Text::Span.styled("error", RatatuiRuby::Style::Style.new(fg: :red)),
+    #   ]))
+    #
+    # [x] X coordinate (Integer, duck-typed via +to_int+).
+    # [y] Y coordinate (Integer, duck-typed via +to_int+).
+    # [line] Text::Line or String.
+    # [max_width] Optional number of columns (Integer, duck-typed via +to_int+).
+    def self.line(x, y, line, max_width = nil)
+      LineCmd.new(x: Integer(x), y: Integer(y), line:, max_width: max_width && Integer(max_width))
+    end
+
+    # Creates a fill draw command.
+    #
+    # [area] Layout::Rect to fill.
+    # [symbol] Optional String (default: a space).
+    # [style] Optional style (Hash or Style).
+    def self.fill(area, symbol = " ", style = {}) = FillCmd.new(area:, symbol: symbol.to_s, style:)
+
+    # Creates a style patch draw command.
+    #
+    # [area] Layout::Rect to restyle.
+    # [style] Style (Hash or Style).
+    def self.style(area, style) = StyleCmd.new(area:, style:)
+
+    # Creates a box draw command.
+    #
+    #   RatatuiRuby::Draw.box(area, border_type: :rounded, style: { fg: :cyan })
+    #
+    # [area] Layout::Rect to draw the border around.
+    # [border_type] Symbol (default: <tt>:plain</tt>).
+    # [border_set] Hash of border characters (optional).
+    # [style] Border style (Hash or Style, optional).
+    def self.box(area, border_type: :plain, border_set: nil, style: {})
+      BoxCmd.new(area:, border_type:, border_set:, style:)
+    end
+
+    # Creates a clear draw command.
+    #
+    # [area] Layout::Rect to clear.
+    def self.clear(area) = ClearCmd.new(area:)
   end
 end
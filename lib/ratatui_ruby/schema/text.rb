@@ -52,7 +52,10 @@ module RatatuiRuby
     # === Examples
     #
     #   Text::Span.new(content: "Error", style: Style.new(fg: :red, modifiers: [:bold]))
-    class Span < Data.define(:content, :style)
+    #
+    #   # Clickable in terminals that support OSC 8 hyperlinks
+    #   Text::Span.new(content: "docs", hyperlink: "https://ratatui.rs")
+    class Span < Data.define(:content, :style, :hyperlink)
       ##
       # :attr_reader: content
       # The text content.
@@ -61,11 +64,23 @@ module RatatuiRuby
       # :attr_reader: style
       # The style to apply.
 
+      ##
+      # :attr_reader: hyperlink
+      # The URL the span links to, or +nil+.
+      #
+      # Terminals that support OSC 8 make the text clickable. Others show it as plain
+      # text. Use a <tt>file://</tt> URL for local paths. The link does not change how
+      # the text looks, so style it as a link yourself.
+      #
+      # About 127 distinct links can be on screen at once. Spans beyond that show as
+      # plain text.
+
       # Creates a new Span.
       #
       # [content] String.
       # [style] Style object (optional).
-      def initialize(content:, style: nil)
+      # [hyperlink] String URL (optional).
+      def initialize(content:, style: nil, hyperlink: nil)
         super
       end
 
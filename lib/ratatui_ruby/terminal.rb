@@ -0,0 +1,130 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  # A terminal to draw on and read events from.
+  #
+  # The module-level methods (<tt>RatatuiRuby.draw</tt>, <tt>RatatuiRuby.poll_event</tt>, ...)
+  # all share one terminal per process. A process can only show one screen, and tests
+  # that use it cannot run in parallel.
+  #
+  # A Terminal created with +new+ is headless. It has its own screen, event queue,
+  # color depth, and draw statistics, and never writes to the real terminal. Create
+  # one per test, or keep several side by side. {default} stands for the module-level
+  # terminal, so code written against a Terminal works with either.
+  #
+  # Headless terminals only receive events from {inject_event}. Mouse capture, titles,
+  # the clipboard, and cursor shapes belong to the module-level terminal.
+  #
+  # Only the module-level terminal draws on a real screen. Raw mode, input, and crash
+  # recovery belong to the whole process, so a second real terminal cannot be created.
+  #
+  # === Examples
+  #
+  #   terminal = RatatuiRuby::Terminal.new(40, 10)
+  #   terminal.inject_event(RatatuiRuby::Event::Key.new(code: "j"))
+  #   app.update(terminal.poll_event)
+  #   terminal.draw { |frame| app.render(frame) }
+  #   terminal.buffer_content.first # => "┌Inbox───────..."
+  #
+  #   # The same app on the real terminal
+  #   RatatuiRuby.run { app.run(RatatuiRuby::Terminal.default) }
+  class Terminal
+    # Creates a headless terminal.
+    #
+    # [width] Integer number of columns.
+    # [height] Integer number of rows.
+    # [viewport] a {Viewport} selecting where to draw (default: full screen).
+    # [color_depth] the color depth to simulate (default: <tt>:truecolor</tt>).
+    #               See <tt>RatatuiRuby.init_terminal</tt>.
+    def self.new(width, height, viewport: nil, color_depth: :truecolor)
+      _new(width, height, Viewport.resolve(viewport), color_depth)
+    end
+
+    # Returns the Terminal that forwards to the module-level methods.
+    def self.default
+      @default ||= _default
+    end
+
+    ##
+    # :method: default?
+    # Whether this is the module-level terminal.
+    #
+    # (Native method implemented in Rust)
+
+    # Draws a frame. Takes the same arguments as <tt>RatatuiRuby.draw</tt>.
+    #
+    # Returns the frame's {DrawStats}.
+    def draw(tree = nil, &block)
+      if tree && block
+        raise ArgumentError, "Cannot provide both a tree and a block to draw"
+      end
+      unless tree || block
+        raise ArgumentError, "Must provide either a tree or a block to draw"
+      end
+
+      stats = tree ? _draw(tree) : _draw(&block)
+      stats && DrawStats.new(**stats)
+    end
+
+    # Returns the {DrawStats} of the last {draw}, or +nil+.
+    def last_draw_stats
+      stats = _last_draw_stats
+      stats && DrawStats.new(**stats)
+    end
+
+    # Returns the next event, or {Event::None}.
+    #
+    # Headless terminals return at once, whatever the +timeout+.
+    def poll_event(timeout: 0.016)
+      raise ArgumentError, "timeout must be non-negative" if timeout && timeout < 0
+
+      Event.from_native(_poll_event(timeout))
+    end
+
+    # Queues +event+ for {poll_event}.
+    def inject_event(event)
+      _inject_event(*Event.to_native(event))
+    end
+
+    ##
+    # :method: clear_events
+    # Drops every queued event.
+    #
+    # (Native method implemented in Rust)
+
+    # Current content of the screen, one String per row.
+    def buffer_content
+      _buffer_content.split("\n")
+    end
+
+    ##
+    # :method: buffer
+    # Returns a copy of the screen as a {Buffer::Buffer}, to export or inspect later.
+    #
+    #   File.write("screen.svg", terminal.buffer.to_svg)
+    #
+    # (Native method implemented in Rust)
+
+    # Returns the {Buffer::Cell} at the given coordinates.
+    def cell_at(x, y)
+      Buffer::Cell.from_native(_cell_at(x, y))
+    end
+
+    # Current cursor position as a Hash with <tt>:x</tt> and <tt>:y</tt> keys.
+    def cursor_position
+      x, y = _cursor_position
+      { x:, y: }
+    end
+
+    ##
+    # :method: resize
+    # :call-seq: resize(width, height) -> nil
+    #
+    # Resizes the screen, as resizing the terminal window would.
+    #
+    # (Native method implemented in Rust)
+  end
+end
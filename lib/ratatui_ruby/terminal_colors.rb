@@ -0,0 +1,51 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  # The colors a terminal reported for itself.
+  #
+  # A theme that looks right on a dark terminal can be unreadable on a light one.
+  # Apps need to know which they are running on before they pick their styles.
+  #
+  # This class holds the answers to <tt>RatatuiRuby.query_terminal_colors</tt>. Colors
+  # are <tt>"#rrggbb"</tt> Strings, or +nil+ when the terminal did not answer.
+  #
+  # === Examples
+  #
+  #   colors = RatatuiRuby.query_terminal_colors
+  #   theme = colors.light? ? LIGHT_THEME : DARK_THEME
+  class TerminalColors < Data.define(:foreground, :background, :palette)
+    ##
+    # :attr_reader: foreground
+    # The default text color (OSC 10), or +nil+.
+
+    ##
+    # :attr_reader: background
+    # The default background color (OSC 11), or +nil+.
+
+    ##
+    # :attr_reader: palette
+    # Hash of each requested palette index (OSC 4) to its color, or +nil+.
+
+    # Whether the background is dark. +nil+ if the background is unknown.
+    def dark?
+      luminance = background_luminance
+      luminance && luminance < 0.5
+    end
+
+    # Whether the background is light. +nil+ if the background is unknown.
+    def light?
+      luminance = background_luminance
+      luminance && luminance >= 0.5
+    end
+
+    private def background_luminance
+      return unless background
+
+      r, g, b = background.delete_prefix("#").scan(/../).map { |hex| hex.to_i(16) / 255.0 }
+      (0.2126 * r) + (0.7152 * g) + (0.0722 * b)
+    end
+  end
+end
@@ -51,28 +51,7 @@ module RatatuiRuby
             "is flushed before the application starts."
         end
 
-        case event
-        when RatatuiRuby::Event::Key
-          RatatuiRuby.inject_test_event("key", { code: event.code, modifiers: event.modifiers })
-        when RatatuiRuby::Event::Mouse
-          RatatuiRuby.inject_test_event("mouse", {
-            kind: event.kind,
-            button: event.button,
-            x: event.x,
-            y: event.y,
-            modifiers: event.modifiers,
-          })
-        when RatatuiRuby::Event::Resize
-          RatatuiRuby.inject_test_event("resize", { width: event.width, height: event.height })
-        when RatatuiRuby::Event::Paste
-          RatatuiRuby.inject_test_event("paste", { content: event.content })
-        when RatatuiRuby::Event::FocusGained
-          RatatuiRuby.inject_test_event("focus_gained", {})
-        when RatatuiRuby::Event::FocusLost
-          RatatuiRuby.inject_test_event("focus_lost", {})
-        else
-          raise ArgumentError, "Unknown event type: #{event.class}"
-        end
+        RatatuiRuby.inject_test_event(*RatatuiRuby::Event.to_native(event))
       end
 
       ##
@@ -86,7 +65,7 @@ module RatatuiRuby
       # [y] Integer y-coordinate.
       # [kind] Symbol <tt>:down</tt>, <tt>:up</tt>, or <tt>:drag</tt>.
       # [button] Symbol <tt>:left</tt>, <tt>:right</tt>, or <tt>:middle</tt>.
-      # [modifiers] Array of modifier strings.
+      # [modifiers] Array of modifier strings (see RatatuiRuby::Event::MODIFIERS).
       def inject_mouse(x:, y:, kind: :down, modifiers: [], button: :left)
         event = RatatuiRuby::Event::Mouse.new(
           kind: kind.to_s,
@@ -133,7 +112,7 @@ module RatatuiRuby
       #
       # Accepts multiple formats for convenience:
       # - String: Character key (e.g., <tt>"a"</tt>, <tt>"q"</tt>)
-      # - Symbol: Named key or modifier combo (e.g., <tt>:enter</tt>, <tt>:ctrl_c</tt>)
+      # - Symbol: Named key or modifier combo (e.g., <tt>:enter</tt>, <tt>:ctrl_c</tt>, <tt>:super_page_up</tt>)
       # - Hash: Passed to <tt>Key.new</tt>
       # - Key: Passed directly
       #
@@ -150,9 +129,9 @@ module RatatuiRuby
                     RatatuiRuby::Event::Key.new(code: arg)
                   when Symbol
                     parts = arg.to_s.split("_")
-                    code = parts.pop
-                    modifiers = parts
-                    RatatuiRuby::Event::Key.new(code:, modifiers:)
+                    modifiers = []
+                    modifiers << parts.shift while parts.size > 1 && RatatuiRuby::Event::MODIFIERS.include?(parts.first)
+                    RatatuiRuby::Event::Key.new(code: parts.join("_"), modifiers:)
                   when Hash
                     RatatuiRuby::Event::Key.new(**arg)
                   when RatatuiRuby::Event::Key
@@ -39,6 +39,8 @@ module RatatuiRuby
       # [width] Integer width of the test terminal (default: 80).
       # [height] Integer height of the test terminal (default: 24).
       # [timeout] Integer maximum execution time in seconds (default: 2). Pass <tt>nil</tt> to disable.
+      # [viewport] RatatuiRuby::Viewport to simulate (default: full screen).
+      # [color_depth] Color depth to simulate (default: <tt>:truecolor</tt>).
       #
       # === Example
       #
@@ -46,7 +48,7 @@ module RatatuiRuby
       #     # render and test your app
       #   end
       def with_test_terminal(width = 80, height = 24, **opts)
-        RatatuiRuby.init_test_terminal(width, height)
+        RatatuiRuby.init_test_terminal(width, height, viewport: opts[:viewport], color_depth: opts.fetch(:color_depth, :truecolor))
         # Flush any lingering events from previous tests
         while (event = RatatuiRuby.poll_event) && !event.none?; end
 
@@ -93,6 +95,40 @@ module RatatuiRuby
         { x:, y: }
       end
 
+      ##
+      # Current cursor style as a hash with <tt>:shape</tt>, <tt>:blinking</tt>,
+      # and <tt>:visible</tt> keys.
+      #
+      # === Example
+      #
+      #   cursor_style
+      #   # => { shape: :bar, blinking: true, visible: true }
+      def cursor_style
+        RatatuiRuby.get_cursor_style
+      end
+
+      ##
+      # Title set with <tt>RatatuiRuby.set_title</tt>, or +nil+.
+      #
+      # === Example
+      #
+      #   terminal_title
+      #   # => "notes.md - Editor"
+      def terminal_title
+        RatatuiRuby.get_title
+      end
+
+      ##
+      # Strings copied with <tt>RatatuiRuby.copy_to_clipboard</tt>, oldest first.
+      #
+      # === Example
+      #
+      #   clipboard_writes
+      #   # => ["first line"]
+      def clipboard_writes
+        RatatuiRuby.get_clipboard_writes
+      end
+
       ##
       # Cell attributes at the given coordinates.
       #
@@ -16,23 +16,71 @@ module RatatuiRuby
         RatatuiRuby.draw(tree, &)
       end
 
+      # Prints permanent lines above an inline viewport.
+      # @see RatatuiRuby.insert_before
+      def insert_before(height, tree = nil, &)
+        RatatuiRuby.insert_before(height, tree, &)
+      end
+
       # Checks for user input.
       # @see RatatuiRuby.poll_event
       def poll_event(timeout: 0.016)
         RatatuiRuby.poll_event(timeout:)
       end
 
+      # Hands the terminal back to the shell, runs the block, and returns to TUI mode.
+      # @see RatatuiRuby.suspend
+      def suspend(&)
+        RatatuiRuby.suspend(&)
+      end
+
+      # Sets the terminal window title.
+      # @see RatatuiRuby.set_title
+      def set_title(title)
+        RatatuiRuby.set_title(title)
+      end
+
+      # Copies text to the system clipboard.
+      # @see RatatuiRuby.copy_to_clipboard
+      def copy_to_clipboard(text, selection: :clipboard)
+        RatatuiRuby.copy_to_clipboard(text, selection:)
+      end
+
+      # Asks the terminal for its default colors and palette entries.
+      # @see RatatuiRuby.query_terminal_colors
+      def query_terminal_colors(palette: [], timeout: 0.1)
+        RatatuiRuby.query_terminal_colors(palette:, timeout:)
+      end
+
+      # Returns an IO that is readable whenever an event is pending.
+      # @see RatatuiRuby.event_io
+      def event_io
+        RatatuiRuby.event_io
+      end
+
       # Inspects the terminal buffer at specific coordinates.
       # @see RatatuiRuby.get_cell_at
       def get_cell_at(x, y)
         RatatuiRuby.get_cell_at(x, y)
       end
 
+      # Renders a widget into a standalone buffer.
+      # @see RatatuiRuby.render_to_buffer
+      def render_to_buffer(...)
+        RatatuiRuby.render_to_buffer(...)
+      end
+
       # Creates a Draw::CellCmd for placing a cell at coordinates.
       # @return [Draw::CellCmd]
       def draw_cell(x, y, cell)
         Draw.cell(x, y, cell)
       end
+
+      # Creates a Draw::WidgetCmd for rendering a widget into an area.
+      # @return [Draw::WidgetCmd]
+      def draw_widget(widget, area)
+        Draw.widget(widget, area)
+      end
     end
   end
 end
@@ -0,0 +1,100 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+module RatatuiRuby
+  # Defines which part of the terminal the application draws to.
+  #
+  # Full-screen apps take over the terminal. That is wrong for CLI tools that
+  # show a progress bar or a picker below the shell prompt. Those tools need a
+  # small live region that stays in scrollback when the program exits.
+  #
+  # This class selects the drawing region. It mirrors +ratatui::Viewport+.
+  #
+  # Pass it to <tt>RatatuiRuby.init_terminal</tt> or <tt>RatatuiRuby.run</tt>.
+  #
+  # === Examples
+  #
+  #   # Take over the whole screen (the default)
+  #   RatatuiRuby.run(viewport: Viewport.fullscreen) { |tui| ... }
+  #
+  #   # Draw 3 lines below the shell prompt
+  #   RatatuiRuby.run(viewport: Viewport.inline(3)) { |tui| ... }
+  #
+  #   # Draw into a fixed region of the main screen
+  #   area = Layout::Rect.new(x: 0, y: 0, width: 40, height: 10)
+  #   RatatuiRuby.run(viewport: Viewport.fixed(area)) { |tui| ... }
+  class Viewport < Data.define(:type, :height, :area)
+    ##
+    # :attr_reader: type
+    # The kind of viewport.
+    #
+    # <tt>:fullscreen</tt>, <tt>:inline</tt>, or <tt>:fixed</tt>.
+
+    ##
+    # :attr_reader: height
+    # Number of rows for an <tt>:inline</tt> viewport (Integer), otherwise +nil+.
+
+    ##
+    # :attr_reader: area
+    # The region for a <tt>:fixed</tt> viewport (Layout::Rect), otherwise +nil+.
+
+    # Uses the whole terminal inside the alternate screen.
+    #
+    #   Viewport.fullscreen
+    def self.fullscreen
+      new(type: :fullscreen, height: nil, area: nil)
+    end
+
+    # Uses a fixed number of rows below the cursor, on the main screen.
+    #
+    # The final frame stays in scrollback after <tt>restore_terminal</tt>.
+    #
+    #   Viewport.inline(5)
+    #
+    # [height] Number of rows (Integer).
+    def self.inline(height)
+      new(type: :inline, height: Integer(height), area: nil)
+    end
+
+    # Uses a fixed rectangle of the main screen.
+    #
+    #   Viewport.fixed(Layout::Rect.new(x: 0, y: 0, width: 40, height: 10))
+    #
+    # [area] The region to draw to (Layout::Rect).
+    def self.fixed(area)
+      new(type: :fixed, height: nil, area:)
+    end
+
+    # Converts a <tt>viewport:</tt> option into a Viewport.
+    #
+    # Accepts +nil+ or <tt>:fullscreen</tt> (full screen) and Viewport objects.
+    #
+    #   Viewport.resolve(nil)                # => Viewport.fullscreen
+    #   Viewport.resolve(Viewport.inline(4)) # => Viewport.inline(4)
+    def self.resolve(value)
+      case value
+      when nil, :fullscreen then fullscreen
+      when Viewport then value
+      else
+        raise ArgumentError, "Unknown viewport: #{value.inspect}"
+      end
+    end
+
+    # Returns true for full-screen viewports.
+    def fullscreen?
+      type == :fullscreen
+    end
+
+    # Returns true for inline viewports.
+    def inline?
+      type == :inline
+    end
+
+    # Returns true for fixed viewports.
+    def fixed?
+      type == :fixed
+    end
+  end
+end
@@ -11,17 +11,24 @@ module RatatuiRuby
     #
     # This widget renders a ghost. It does not draw a character but instructs the terminal to place the hardware cursor at specific coordinates.
     #
-    # Use it for text editors, input fields, or command prompts.
+    # Use it for text editors, input fields, or command prompts. Modal editors can switch
+    # between a block cursor for normal mode and a bar cursor for insert mode.
     #
     # === Examples
     #
     #   Cursor.new(x: 10, y: 5)
     #
+    #   # Insert mode
+    #   Cursor.new(x: 10, y: 5, shape: :bar, blinking: true)
+    #
+    #   # Keep the position but hide the cursor, e.g. while a dialog has focus
+    #   Cursor.new(x: 10, y: 5, visible: false)
+    #
     # See also:
     # - {Declarative implementation using Tree API}[link:/examples/app_login_form/app_rb.html]
     # - {Component-based implementation using Frame API}[link:/examples/app_color_picker/app_rb.html]
     # - RatatuiRuby::Frame#set_cursor_position (Frame API alternative)
-    class Cursor < Data.define(:x, :y)
+    class Cursor < Data.define(:x, :y, :shape, :blinking, :visible)
       ##
       # :attr_reader: x
       # X coordinate (column).
@@ -30,12 +37,28 @@ module RatatuiRuby
       # :attr_reader: y
       # Y coordinate (row).
 
+      ##
+      # :attr_reader: shape
+      # Cursor shape (<tt>:default</tt>, <tt>:block</tt>, <tt>:underline</tt>, <tt>:bar</tt>),
+      # or +nil+ to keep the current one.
+
+      ##
+      # :attr_reader: blinking
+      # Whether the cursor blinks. Only used with a +shape+.
+
+      ##
+      # :attr_reader: visible
+      # Whether the cursor is shown. A hidden cursor hides it for the whole frame.
+
       # Creates a new Cursor.
       #
       # [x] Integer.
       # [y] Integer.
-      def initialize(x:, y:)
-        super(x: Integer(x), y: Integer(y))
+      # [shape] Symbol or +nil+ (default: +nil+).
+      # [blinking] Boolean (default: +false+).
+      # [visible] Boolean (default: +true+).
+      def initialize(x:, y:, shape: nil, blinking: false, visible: true)
+        super(x: Integer(x), y: Integer(y), shape:, blinking:, visible:)
       end
     end
   end
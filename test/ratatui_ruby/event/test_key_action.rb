@@ -0,0 +1,99 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+module RatatuiRuby
+  ##
+  # Tests for the Key `action` and `state` attributes reported by the Kitty keyboard protocol.
+  class TestKeyAction < Minitest::Test
+    include RatatuiRuby::TestHelper
+
+    def test_defaults_to_press
+      event = Event::Key.new(code: "a")
+      assert_equal :press, event.action
+      assert_empty event.state
+      assert_predicate event, :press?
+      refute_predicate event, :repeat?
+      refute_predicate event, :release?
+    end
+
+    def test_release_does_not_match_key
+      event = Event::Key.new(code: "q", action: :release)
+      assert_predicate event, :release?
+      assert_equal :q, event.to_sym
+      refute event == :q
+      refute event == "q"
+      refute_predicate event, :q?
+    end
+
+    def test_repeat_matches_key
+      event = Event::Key.new(code: "q", modifiers: ["ctrl"], action: :repeat)
+      assert_equal event, :ctrl_q
+      assert_equal "q", event.code
+      assert_predicate event, :ctrl_q?
+    end
+
+    def test_inspect_shows_non_default_action_and_state
+      event = Event::Key.new(code: "a", action: :repeat, state: [:caps_lock])
+      assert_includes event.inspect, "action=:repeat"
+      assert_includes event.inspect, "state=[:caps_lock]"
+      refute_includes Event::Key.new(code: "a").inspect, "action="
+    end
+
+    def test_pattern_matching_on_action
+      event = Event::Key.new(code: "w", action: :release)
+      matched = case event
+                in { type: :key, code: "w", action: :press | :repeat } then :moving
+                in { type: :key, code: "w", action: :release } then :stopped
+      end
+      assert_equal :stopped, matched
+    end
+
+    def test_action_and_state_round_trip_through_injection
+      with_test_terminal do
+        inject_event(Event::Key.new(code: "a", action: :repeat, state: [:keypad, :num_lock]))
+        inject_event(Event::Key.new(code: "a", action: :release))
+
+        repeat = RatatuiRuby.poll_event
+        assert_equal :repeat, repeat.action
+        assert_equal [:keypad, :num_lock], repeat.state
+
+        release = RatatuiRuby.poll_event
+        assert_predicate release, :release?
+        assert_empty release.state
+      end
+    end
+
+    def test_injected_press_has_no_state
+      with_test_terminal do
+        inject_keys("a")
+        event = RatatuiRuby.poll_event
+        assert_predicate event, :press?
+        assert_empty event.state
+      end
+    end
+
+    def test_unknown_action_raises
+      with_test_terminal do
+        assert_raises(ArgumentError) do
+          RatatuiRuby.inject_test_event("key", { code: "a", action: "tap" })
+        end
+        assert_raises(ArgumentError) do
+          RatatuiRuby.inject_test_event("key", { code: "a", state: ["scroll_lock"] })
+        end
+      end
+    end
+
+    def test_keyboard_enhancement_flags
+      assert_equal %i[
+        disambiguate_escape_codes
+        report_event_types
+        report_alternate_keys
+        report_all_keys_as_escape_codes
+      ], RatatuiRuby::KEYBOARD_ENHANCEMENT_FLAGS
+    end
+  end
+end
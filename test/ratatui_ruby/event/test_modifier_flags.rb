@@ -0,0 +1,80 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+module RatatuiRuby
+  ##
+  # Round-trip tests for modifier *flags* (held modifiers) on key and mouse events.
+  class TestModifierFlags < Minitest::Test
+    include RatatuiRuby::TestHelper
+
+    def test_every_modifier_round_trips_on_keys
+      with_test_terminal do
+        Event::MODIFIERS.each do |modifier|
+          inject_keys({ code: "s", modifiers: [modifier] })
+          event = RatatuiRuby.poll_event
+
+          assert_equal [modifier], event.modifiers
+          assert event.public_send(:"#{modifier}?")
+        end
+      end
+    end
+
+    def test_all_modifiers_at_once
+      with_test_terminal do
+        inject_keys({ code: "s", modifiers: Event::MODIFIERS })
+        event = RatatuiRuby.poll_event
+
+        assert_equal %w[alt ctrl hyper meta shift super], event.modifiers
+        assert_equal :alt_ctrl_hyper_meta_shift_super_s, event.to_sym
+      end
+    end
+
+    def test_super_binding
+      with_test_terminal do
+        inject_keys(:ctrl_super_s)
+        event = RatatuiRuby.poll_event
+
+        assert_equal :ctrl_super_s, event.to_sym
+        assert_predicate event, :cmd?
+      end
+    end
+
+    def test_symbol_injection_keeps_multi_word_codes
+      with_test_terminal do
+        inject_keys(:super_page_up, :media_play)
+
+        event = RatatuiRuby.poll_event
+        assert_equal "page_up", event.code
+        assert_equal ["super"], event.modifiers
+
+        event = RatatuiRuby.poll_event
+        assert_equal "media_play", event.code
+        assert_empty event.modifiers
+      end
+    end
+
+    def test_every_modifier_round_trips_on_mouse
+      with_test_terminal do
+        inject_click(x: 1, y: 2, modifiers: Event::MODIFIERS)
+        event = RatatuiRuby.poll_event
+
+        assert_equal %w[alt ctrl hyper meta shift super], event.modifiers
+      end
+    end
+
+    def test_unknown_modifier_raises
+      with_test_terminal do
+        assert_raises(ArgumentError) do
+          RatatuiRuby.inject_test_event("key", { code: "a", modifiers: ["cmd"] })
+        end
+        assert_raises(ArgumentError) do
+          RatatuiRuby.inject_test_event("mouse", { kind: "down", x: 0, y: 0, modifiers: ["option"] })
+        end
+      end
+    end
+  end
+end
@@ -63,4 +63,80 @@ class TestDraw < Minitest::Test
       assert_equal " ", RatatuiRuby.get_cell_at(9, 4).char # Unaffected
     end
   end
+
+  class CommandsWidget
+    def initialize(&block)
+      @block = block
+    end
+
+    def render(area)
+      @block.call(area)
+    end
+  end
+
+  def rect(x, y, width, height)
+    RatatuiRuby::Layout::Rect.new(x:, y:, width:, height:)
+  end
+
+  # Renders the widget into the inner 4x2 of a 6x4 buffer filled with dots.
+  def render_inset(&block)
+    RatatuiRuby.render_to_buffer(width: 6, height: 4) do |frame|
+      frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: (["." * 6] * 4).join("\n")), frame.area)
+      frame.render_widget(CommandsWidget.new(&block), rect(1, 1, 4, 2))
+    end
+  end
+
+  def test_draw_line
+    line = RatatuiRuby::Text::Line.new(spans: [
+      RatatuiRuby::Text::Span.new(content: "ab", style: RatatuiRuby::Style::Style.new(fg: :red)),
+      RatatuiRuby::Text::Span.new(content: "cdef"),
+    ])
+    buffer = render_inset { |area| [RatatuiRuby::Draw.line(area.x, area.y, line), RatatuiRuby::Draw.line(area.x, area.y + 1, "xyz", 2)] }
+    assert_equal ["......", ".abcd.", ".xy...", "......"], buffer.lines
+    assert_equal :red, buffer.cell_at(1, 1).fg
+    assert_nil buffer.cell_at(3, 1).fg
+  end
+
+  def test_draw_line_outside_the_area
+    buffer = render_inset { |area| [RatatuiRuby::Draw.line(area.x, area.y - 1, "no")] }
+    assert_equal ["......"] * 4, buffer.lines
+  end
+
+  def test_draw_fill
+    buffer = render_inset { |_area| [RatatuiRuby::Draw.fill(rect(0, 0, 3, 3), "#", { bg: :blue })] }
+    assert_equal ["......", ".##...", ".##...", "......"], buffer.lines
+    assert_equal :blue, buffer.cell_at(2, 2).bg
+  end
+
+  def test_draw_style
+    buffer = render_inset { |area| [RatatuiRuby::Draw.style(area, { fg: :green })] }
+    assert_equal ["......"] * 4, buffer.lines
+    assert_equal :green, buffer.cell_at(4, 2).fg
+    assert_nil buffer.cell_at(5, 2).fg
+  end
+
+  def test_draw_box
+    buffer = render_inset { |area| [RatatuiRuby::Draw.box(area, border_type: :rounded, style: { fg: :cyan })] }
+    assert_equal ["......", ".╭──╮.", ".╰──╯.", "......"], buffer.lines
+    assert_equal :cyan, buffer.cell_at(1, 1).fg
+  end
+
+  def test_draw_box_keeps_the_inside_and_clips
+    buffer = render_inset do |area|
+      [RatatuiRuby::Draw.box(rect(area.x + 1, area.y, 4, 3), border_set: { top_left: "+", horizontal_top: "=", vertical_left: "|" })]
+    end
+    assert_equal ["......", "..+==.", "..|...", "......"], buffer.lines
+  end
+
+  def test_draw_clear
+    buffer = render_inset { |_area| [RatatuiRuby::Draw.clear(rect(3, 0, 6, 6))] }
+    assert_equal ["......", "...  .", "...  .", "......"], buffer.lines
+  end
+
+  def test_commands_run_in_order
+    buffer = render_inset do |area|
+      [RatatuiRuby::Draw.fill(area, "*"), RatatuiRuby::Draw.clear(rect(area.x, area.y, 1, 1))]
+    end
+    assert_equal ["......", ". ***.", ".****.", "......"], buffer.lines
+  end
 end
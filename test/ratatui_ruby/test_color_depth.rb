@@ -0,0 +1,77 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestColorDepth < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def draw_colored
+    style = RatatuiRuby::Style::Style.new(fg: "#ff0000", bg: "#00ff00")
+    RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "Hi", style:))
+    RatatuiRuby.get_cell_at(0, 0)
+  end
+
+  def test_test_terminal_defaults_to_truecolor
+    with_test_terminal(4, 1) do
+      assert_equal :truecolor, RatatuiRuby.color_depth
+      cell = draw_colored
+      assert_equal "#ff0000", cell.fg
+      assert_equal "#00ff00", cell.bg
+    end
+  end
+
+  def test_ansi256_maps_rgb_to_palette
+    with_test_terminal(4, 1, color_depth: :ansi256) do
+      assert_equal :ansi256, RatatuiRuby.color_depth
+      cell = draw_colored
+      assert_equal :indexed_196, cell.fg
+      assert_equal :indexed_46, cell.bg
+    end
+  end
+
+  def test_ansi16_maps_to_named_colors
+    with_test_terminal(4, 1, color_depth: :ansi16) do
+      cell = draw_colored
+      assert_equal :light_red, cell.fg
+      assert_equal :light_green, cell.bg
+    end
+  end
+
+  def test_ansi16_keeps_named_colors
+    with_test_terminal(4, 1, color_depth: :ansi16) do
+      style = RatatuiRuby::Style::Style.new(fg: :blue)
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "Hi", style:))
+      assert_equal :blue, RatatuiRuby.get_cell_at(0, 0).fg
+    end
+  end
+
+  def test_none_strips_colors_but_keeps_modifiers
+    with_test_terminal(4, 1, color_depth: :none) do
+      style = RatatuiRuby::Style::Style.new(fg: "#ff0000", bg: :blue, modifiers: [:bold])
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "Hi", style:))
+      cell = RatatuiRuby.get_cell_at(0, 0)
+      assert_nil cell.fg
+      assert_nil cell.bg
+      assert_equal ["bold"], cell.modifiers
+    end
+  end
+
+  def test_frame_api_is_downsampled
+    with_test_terminal(4, 1, color_depth: :ansi256) do
+      RatatuiRuby.draw do |frame|
+        style = RatatuiRuby::Style::Style.new(fg: "#ff0000")
+        frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "Hi", style:), frame.area)
+      end
+      assert_equal :indexed_196, RatatuiRuby.get_cell_at(0, 0).fg
+    end
+  end
+
+  def test_unknown_depth_raises
+    assert_raises(ArgumentError) do
+      RatatuiRuby.init_test_terminal(4, 1, color_depth: :sixteen)
+    end
+  end
+end
@@ -0,0 +1,80 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestCursorStyle < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_defaults_to_hidden_user_shape
+    with_test_terminal do
+      RatatuiRuby.draw { |_frame| nil }
+      assert_equal({ shape: :default, blinking: false, visible: false }, cursor_style)
+    end
+  end
+
+  def test_frame_sets_shape
+    with_test_terminal do
+      RatatuiRuby.draw do |frame|
+        frame.set_cursor_style(:bar, blinking: true)
+        frame.set_cursor_position(1, 1)
+      end
+      assert_equal({ shape: :bar, blinking: true, visible: true }, cursor_style)
+    end
+  end
+
+  def test_shape_persists_across_frames
+    with_test_terminal do
+      RatatuiRuby.draw { |frame| frame.set_cursor_style(:underline) }
+      RatatuiRuby.draw { |frame| frame.set_cursor_position(0, 0) }
+      assert_equal :underline, cursor_style[:shape]
+    end
+  end
+
+  def test_hide_cursor_overrides_position
+    with_test_terminal do
+      RatatuiRuby.draw do |frame|
+        frame.set_cursor_position(3, 1)
+        frame.hide_cursor
+      end
+      refute cursor_style[:visible]
+
+      RatatuiRuby.draw { |frame| frame.set_cursor_position(3, 1) }
+      assert cursor_style[:visible]
+    end
+  end
+
+  def test_cursor_widget_sets_shape
+    with_test_terminal(10, 5) do
+      cursor = RatatuiRuby::Widgets::Cursor.new(x: 2, y: 1, shape: :block)
+      RatatuiRuby.draw(cursor)
+      assert_equal({ shape: :block, blinking: false, visible: true }, cursor_style)
+      assert_equal({ x: 2, y: 1 }, cursor_position)
+    end
+  end
+
+  def test_cursor_widget_can_hide
+    with_test_terminal(10, 5) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Cursor.new(x: 2, y: 1, visible: false))
+      refute cursor_style[:visible]
+    end
+  end
+
+  def test_unknown_shape
+    with_test_terminal do
+      error = assert_raises(ArgumentError) do
+        RatatuiRuby.draw { |frame| frame.set_cursor_style(:beam) }
+      end
+      assert_equal "Unknown cursor shape: beam", error.message
+    end
+  end
+
+  def test_restore_resets_shape
+    with_test_terminal do
+      RatatuiRuby.draw { |frame| frame.set_cursor_style(:bar) }
+    end
+    assert_equal :default, RatatuiRuby.get_cursor_style[:shape]
+  end
+end
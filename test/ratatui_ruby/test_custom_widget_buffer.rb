@@ -0,0 +1,212 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+require "delegate"
+
+class TestCustomWidgetBuffer < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  # A custom widget that writes through the buffer it is given.
+  class BufferWidget
+    attr_reader :buffer
+
+    def initialize(&block)
+      @block = block
+    end
+
+    def render(area, buffer)
+      @buffer = buffer
+      @block.call(area, buffer)
+    end
+  end
+
+  def draw_with(width, height, &)
+    widget = BufferWidget.new(&)
+    with_test_terminal(width, height) do
+      RatatuiRuby.draw(widget)
+      [widget, buffer_content]
+    end
+  end
+
+  def test_set_string
+    _, content = draw_with(6, 1) do |area, buffer|
+      assert_equal [5, 0], buffer.set_string(area.x, area.y, "Hello", { fg: :red })
+    end
+    assert_equal ["Hello "], content
+  end
+
+  def test_set_string_clips_to_the_buffer
+    _, content = draw_with(4, 2) do |_area, buffer|
+      buffer.set_string(2, 0, "abcdef")
+      buffer.set_string(-1, 1, "hidden")
+      buffer.set_string(0, 5, "hidden")
+    end
+    assert_equal ["  ab", "    "], content
+  end
+
+  def test_set_line_keeps_span_styles
+    with_test_terminal(6, 1) do
+      RatatuiRuby.draw(BufferWidget.new do |_area, buffer|
+        line = RatatuiRuby::Text::Line.new(spans: [
+          RatatuiRuby::Text::Span.new(content: "ab", style: RatatuiRuby::Style::Style.new(fg: :green)),
+          RatatuiRuby::Text::Span.new(content: "cd"),
+        ])
+        buffer.set_line(0, 0, line, 3)
+      end)
+      assert_equal ["abc   "], buffer_content
+      assert_equal :green, RatatuiRuby.get_cell_at(0, 0).fg
+      assert_nil RatatuiRuby.get_cell_at(2, 0).fg
+    end
+  end
+
+  def test_reads_what_is_underneath
+    with_test_terminal(5, 1) do
+      seen = nil
+      RatatuiRuby.draw do |frame|
+        frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "under"), frame.area)
+        frame.render_widget(BufferWidget.new { |_area, buffer| seen = buffer.cell_at(0, 0).char }, frame.area)
+      end
+      assert_equal "u", seen
+    end
+  end
+
+  def test_set_style_patches_existing_cells
+    with_test_terminal(4, 1) do
+      RatatuiRuby.draw(BufferWidget.new do |area, buffer|
+        buffer.set_string(0, 0, "abcd", { fg: :red })
+        buffer.set_style(RatatuiRuby::Layout::Rect.new(x: 2, y: 0, width: 10, height: 1), { bg: :blue })
+        buffer.set_style(area, { modifiers: [:bold] })
+      end)
+      assert_equal ["abcd"], buffer_content
+      assert_equal :red, RatatuiRuby.get_cell_at(3, 0).fg
+      assert_nil RatatuiRuby.get_cell_at(1, 0).bg
+      assert_equal :blue, RatatuiRuby.get_cell_at(3, 0).bg
+      assert RatatuiRuby.get_cell_at(0, 0).bold?
+    end
+  end
+
+  def test_set_cell
+    _, content = draw_with(3, 1) do |_area, buffer|
+      buffer.set_cell(1, 0, RatatuiRuby::Buffer::Cell.new(char: "#", fg: :yellow))
+      buffer.set_cell(9, 9, RatatuiRuby::Buffer::Cell.new(char: "!"))
+    end
+    assert_equal [" # "], content
+  end
+
+  def test_area_is_the_frame_buffer
+    with_test_terminal(7, 3) do
+      area = nil
+      RatatuiRuby.draw(BufferWidget.new { |_area, buffer| area = buffer.area })
+      assert_equal RatatuiRuby::Layout::Rect.new(x: 0, y: 0, width: 7, height: 3), area
+    end
+  end
+
+  def test_buffer_is_unusable_after_render
+    widget, = draw_with(3, 1) { |_area, _buffer| nil }
+    assert_raises(RatatuiRuby::Error::Safety) { widget.buffer.set_string(0, 0, "x") }
+    assert_raises(RatatuiRuby::Error::Safety) { widget.buffer.width }
+  end
+
+  def test_works_inside_layouts
+    with_test_terminal(6, 2) do
+      layout = RatatuiRuby::Layout::Layout.new(
+        direction: :vertical,
+        constraints: [
+          RatatuiRuby::Layout::Constraint.length(1),
+          RatatuiRuby::Layout::Constraint.length(1),
+        ],
+        children: [
+          RatatuiRuby::Widgets::Paragraph.new(text: "top"),
+          BufferWidget.new { |area, buffer| buffer.set_string(area.x, area.y, "bottom") },
+        ]
+      )
+      RatatuiRuby.draw(layout)
+      assert_equal ["top   ", "bottom"], buffer_content
+    end
+  end
+
+  def test_render_widget
+    _, content = draw_with(8, 2) do |area, buffer|
+      buffer.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "child"), area)
+      buffer.render_widget(
+        BufferWidget.new { |inner, inner_buffer| inner_buffer.set_string(inner.x, inner.y, "nested") },
+        RatatuiRuby::Layout::Rect.new(x: 4, y: 1, width: 20, height: 1)
+      )
+    end
+    assert_equal ["child   ", "    nest"], content
+  end
+
+  def test_buffer_is_unusable_while_rendering_a_child
+    outer = nil
+    child = BufferWidget.new { |_area, _buffer| outer.set_string(0, 0, "x") }
+    with_test_terminal(3, 1) do
+      assert_raises(RatatuiRuby::Error::Safety) do
+        RatatuiRuby.draw(BufferWidget.new do |area, buffer|
+          outer = buffer
+          buffer.render_widget(child, area)
+        end)
+      end
+    end
+  end
+
+  def test_buffer_works_again_after_rendering_a_child
+    _, content = draw_with(4, 1) do |area, buffer|
+      buffer.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "ab"), area)
+      buffer.set_string(2, 0, "cd")
+    end
+    assert_equal ["abcd"], content
+  end
+
+  def test_owned_buffers_cannot_render_widgets
+    buffer = RatatuiRuby.render_to_buffer(RatatuiRuby::Widgets::Paragraph.new(text: ""), width: 1, height: 1)
+    assert_raises(NotImplementedError) do
+      buffer.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "x"), RatatuiRuby::Layout::Rect.new(x: 0, y: 0, width: 1, height: 1))
+    end
+  end
+
+  def test_render_to_buffer
+    widget = BufferWidget.new { |area, buffer| buffer.set_string(area.x, area.y, "hi") }
+    assert_equal ["hi "], RatatuiRuby.render_to_buffer(widget, width: 3, height: 1).lines
+  end
+
+  def test_area_only_widgets_still_return_draw_commands
+    widget = Class.new do
+      def render(area)
+        [RatatuiRuby::Draw.string(area.x, area.y, "old")]
+      end
+    end.new
+    assert_equal ["old"], RatatuiRuby.render_to_buffer(widget, width: 3, height: 1).lines
+  end
+
+  def test_delegated_widgets_return_draw_commands
+    widget = Class.new do
+      def render(area)
+        [RatatuiRuby::Draw.string(area.x, area.y, "old")]
+      end
+    end.new
+    delegator = SimpleDelegator.new(widget)
+    assert_equal(-1, delegator.method(:render).arity)
+    assert_equal ["old"], RatatuiRuby.render_to_buffer(delegator, width: 3, height: 1).lines
+  end
+
+  def test_splat_widgets_return_draw_commands
+    widget = Class.new do
+      def render(*args)
+        [RatatuiRuby::Draw.string(args.first.x, args.first.y, "old")]
+      end
+    end.new
+    assert_equal ["old"], RatatuiRuby.render_to_buffer(widget, width: 3, height: 1).lines
+  end
+
+  def test_widgets_requiring_a_buffer_and_more_get_the_buffer
+    widget = Class.new do
+      def render(area, buffer, *)
+        buffer.set_string(area.x, area.y, "hi")
+      end
+    end.new
+    assert_equal ["hi "], RatatuiRuby.render_to_buffer(widget, width: 3, height: 1).lines
+  end
+end
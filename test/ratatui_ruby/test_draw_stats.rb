@@ -0,0 +1,70 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestDrawStats < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def paragraph(text)
+    RatatuiRuby::Widgets::Paragraph.new(text:)
+  end
+
+  def test_draw_returns_stats
+    with_test_terminal(10, 2) do
+      stats = RatatuiRuby.draw(paragraph("Hello"))
+      assert_instance_of RatatuiRuby::DrawStats, stats
+      assert_equal 1, stats.frame_count
+      assert_equal 5, stats.cells_changed
+      assert_equal 0, stats.bytes_written
+      assert_operator stats.render_duration, :>, 0
+      assert_in_delta stats.duration, stats.convert_duration + stats.render_duration + stats.output_duration, 1e-6
+    end
+  end
+
+  def test_cells_changed_counts_only_the_diff
+    with_test_terminal(10, 2) do
+      RatatuiRuby.draw(paragraph("Hello"))
+      assert_equal 1, RatatuiRuby.draw(paragraph("Jello")).cells_changed
+      assert_equal 0, RatatuiRuby.draw(paragraph("Jello")).cells_changed
+    end
+  end
+
+  def test_slow_ruby_code_counts_as_converting
+    with_test_terminal(10, 2) do
+      stats = RatatuiRuby.draw do |frame|
+        sleep 0.01
+        frame.render_widget(paragraph("Hi"), frame.area)
+      end
+      assert_operator stats.convert_duration, :>=, 0.01
+      assert_operator stats.render_duration, :<, 0.01
+    end
+  end
+
+  def test_frame_count_increments
+    with_test_terminal(10, 2) do
+      3.times { RatatuiRuby.draw(paragraph("x")) }
+      assert_equal 3, RatatuiRuby.last_draw_stats.frame_count
+    end
+  end
+
+  def test_block_form_returns_stats
+    with_test_terminal(10, 2) do
+      stats = RatatuiRuby.draw { |frame| frame.render_widget(paragraph("Hi"), frame.area) }
+      assert_equal 2, stats.cells_changed
+    end
+  end
+
+  def test_last_draw_stats_resets_with_terminal
+    with_test_terminal(10, 2) do
+      assert_nil RatatuiRuby.last_draw_stats
+      RatatuiRuby.draw(paragraph("x"))
+      refute_nil RatatuiRuby.last_draw_stats
+    end
+    with_test_terminal(10, 2) do
+      assert_nil RatatuiRuby.last_draw_stats
+    end
+  end
+end
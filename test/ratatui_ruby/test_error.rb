@@ -9,6 +9,7 @@ class TestError < Minitest::Test
   def test_inheritance
     assert_operator RatatuiRuby::Error::Terminal, :<, RatatuiRuby::Error
     assert_operator RatatuiRuby::Error::Safety, :<, RatatuiRuby::Error
+    assert_operator RatatuiRuby::Error::Panic, :<, RatatuiRuby::Error
     assert_operator RatatuiRuby::Error, :<, StandardError
   end
 
@@ -15,6 +15,19 @@ class CustomWidget
   end
 end
 
+class CompositeWidget
+  def render(area)
+    top, bottom = RatatuiRuby::Layout::Layout.split(
+      area,
+      constraints: [RatatuiRuby::Layout::Constraint.length(1), RatatuiRuby::Layout::Constraint.fill]
+    )
+    [
+      RatatuiRuby::Draw.widget(RatatuiRuby::Widgets::Paragraph.new(text: "Title"), top),
+      RatatuiRuby::Draw.widget(CustomWidget.new, bottom),
+    ]
+  end
+end
+
 class TestEscapeHatch < Minitest::Test
   include RatatuiRuby::TestHelper
   def test_custom_widget_render
@@ -47,4 +60,23 @@ class TestEscapeHatch < Minitest::Test
       assert_equal "XY        ", buffer_content[1]
     end
   end
+
+  def test_custom_widget_renders_child_widgets
+    with_test_terminal(6, 2) do
+      RatatuiRuby.draw(CompositeWidget.new)
+
+      assert_equal "Title ", buffer_content[0]
+      assert_equal "XY    ", buffer_content[1]
+    end
+  end
+
+  def test_child_widgets_are_clipped_to_the_frame
+    widget = Class.new do
+      def render(area)
+        [RatatuiRuby::Draw.widget(RatatuiRuby::Widgets::Paragraph.new(text: "abcdef"),
+          RatatuiRuby::Layout::Rect.new(x: 2, y: 0, width: 10, height: 5))]
+      end
+    end.new
+    assert_equal ["  ab"], RatatuiRuby.render_to_buffer(widget, width: 4, height: 1).lines
+  end
 end
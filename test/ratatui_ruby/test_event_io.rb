@@ -0,0 +1,56 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestEventIO < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def setup
+    skip "event_io requires a Unix-like platform" if Gem.win_platform?
+  end
+
+  def ready?
+    !IO.select([RatatuiRuby.event_io], nil, nil, 0).nil?
+  end
+
+  def test_returns_the_same_io
+    assert_kind_of IO, RatatuiRuby.event_io
+    assert_same RatatuiRuby.event_io, RatatuiRuby.event_io
+  end
+
+  def test_not_ready_without_events
+    with_test_terminal do
+      refute ready?
+    end
+  end
+
+  def test_ready_after_injection
+    with_test_terminal do
+      inject_keys("a")
+      assert ready?
+    end
+  end
+
+  def test_stays_ready_until_queue_is_drained
+    with_test_terminal do
+      inject_keys("a", "b")
+
+      RatatuiRuby.poll_event
+      assert ready?
+
+      RatatuiRuby.poll_event
+      refute ready?
+    end
+  end
+
+  def test_clear_events_resets_readiness
+    with_test_terminal do
+      inject_keys("a")
+      RatatuiRuby.clear_events
+      refute ready?
+    end
+  end
+end
@@ -0,0 +1,70 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestExport < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def styled(text, **)
+    RatatuiRuby::Widgets::Paragraph.new(text:, style: RatatuiRuby::Style::Style.new(**))
+  end
+
+  def test_to_ansi
+    buffer = RatatuiRuby.render_to_buffer(styled("ab", fg: :red, modifiers: [:bold]), width: 3, height: 2)
+    assert_equal "\e[1;31mab \e[0m\n\e[1;31m   \e[0m\n", buffer.to_ansi
+  end
+
+  def test_to_ansi_plain_text_has_no_escapes
+    buffer = RatatuiRuby.render_to_buffer(RatatuiRuby::Widgets::Paragraph.new(text: "中a"), width: 3, height: 1)
+    assert_equal "中a\n", buffer.to_ansi
+  end
+
+  def test_to_ansi_resets_removed_modifiers
+    line = RatatuiRuby::Text::Line.new(spans: [
+      RatatuiRuby::Text::Span.new(content: "a", style: RatatuiRuby::Style::Style.new(modifiers: [:bold])),
+      RatatuiRuby::Text::Span.new(content: "b", style: RatatuiRuby::Style::Style.new(fg: :gray)),
+    ])
+    buffer = RatatuiRuby.render_to_buffer(RatatuiRuby::Widgets::Paragraph.new(text: line), width: 2, height: 1)
+    assert_equal "\e[1ma\e[0;37mb\e[0m\n", buffer.to_ansi
+  end
+
+  def test_to_ansi_links
+    span = RatatuiRuby::Text::Span.new(content: "x", hyperlink: "https://example.com")
+    line = RatatuiRuby::Text::Line.new(spans: [span])
+    buffer = RatatuiRuby.render_to_buffer(RatatuiRuby::Widgets::Paragraph.new(text: line), width: 2, height: 1)
+    assert_equal "\e]8;;https://example.com\e\\x\e]8;;\e\\ \n", buffer.to_ansi
+  end
+
+  def test_to_html
+    buffer = RatatuiRuby.render_to_buffer(styled("<b>", fg: "#102030", modifiers: [:italic]), width: 3, height: 1)
+    html = buffer.to_html
+    assert html.start_with?("<!DOCTYPE html>")
+    assert_includes html, %(<span style="color:#102030;font-style:italic">&lt;b&gt;</span>)
+  end
+
+  def test_to_svg
+    buffer = RatatuiRuby.render_to_buffer(styled("中a", bg: :blue), width: 3, height: 1)
+    svg = buffer.to_svg
+    assert svg.start_with?(%(<svg xmlns="http://www.w3.org/2000/svg" width="27" height="18"))
+    assert_includes svg, %(<rect x="0" y="0" width="27" height="18" fill="#0000ee"/>)
+    assert_includes svg, %(<text x="0 18" y="14">中a</text>)
+  end
+
+  def test_get_buffer_copies_the_test_terminal
+    with_test_terminal(4, 1) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "one"))
+      buffer = RatatuiRuby.get_buffer
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "two"))
+      assert_equal ["one "], buffer.lines
+    end
+  end
+
+  def test_terminal_buffer
+    terminal = RatatuiRuby::Terminal.new(3, 1)
+    terminal.draw(RatatuiRuby::Widgets::Paragraph.new(text: "hi"))
+    assert_equal "hi \n", terminal.buffer.to_ansi
+  end
+end
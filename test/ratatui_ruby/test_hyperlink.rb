@@ -0,0 +1,87 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestHyperlink < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def link_line
+    RatatuiRuby::Text::Line.new(spans: [
+      RatatuiRuby::Text::Span.new(content: "see "),
+      RatatuiRuby::Text::Span.new(content: "docs", hyperlink: "https://ratatui.rs"),
+    ])
+  end
+
+  def test_renders_plain_text
+    with_test_terminal(10, 1) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: link_line))
+      assert_equal "see docs  ", buffer_content[0]
+    end
+  end
+
+  def test_get_cell_at_reports_target
+    with_test_terminal(10, 1) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: link_line))
+      assert_nil RatatuiRuby.get_cell_at(0, 0).hyperlink
+      assert_equal "https://ratatui.rs", RatatuiRuby.get_cell_at(4, 0).hyperlink
+      assert_equal "https://ratatui.rs", RatatuiRuby.get_cell_at(7, 0).hyperlink
+      assert_nil RatatuiRuby.get_cell_at(8, 0).hyperlink
+    end
+  end
+
+  def test_link_keeps_style_and_modifiers
+    with_test_terminal(10, 1) do
+      span = RatatuiRuby::Text::Span.new(
+        content: "docs",
+        style: RatatuiRuby::Style::Style.new(fg: :blue, modifiers: [:underlined]),
+        hyperlink: "https://ratatui.rs"
+      )
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: span))
+      cell = RatatuiRuby.get_cell_at(0, 0)
+      assert_equal :blue, cell.fg
+      assert_equal ["underlined"], cell.modifiers
+    end
+  end
+
+  def test_changed_target_is_redrawn
+    with_test_terminal(10, 1) do
+      ["https://a.example", "https://b.example"].each do |url|
+        span = RatatuiRuby::Text::Span.new(content: "link", hyperlink: url)
+        RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: span))
+      end
+      assert_equal "https://b.example", RatatuiRuby.get_cell_at(0, 0).hyperlink
+    end
+  end
+
+  def test_list_items_and_table_cells
+    with_test_terminal(20, 2) do
+      span = RatatuiRuby::Text::Span.new(content: "item", hyperlink: "file:///tmp/log")
+      RatatuiRuby.draw do |frame|
+        top, bottom = RatatuiRuby::Layout::Layout.split(
+          frame.area,
+          direction: :vertical,
+          constraints: [RatatuiRuby::Layout::Constraint.length(1), RatatuiRuby::Layout::Constraint.length(1)]
+        )
+        frame.render_widget(RatatuiRuby::Widgets::List.new(items: [span]), top)
+        frame.render_widget(
+          RatatuiRuby::Widgets::Table.new(rows: [[span]], widths: [RatatuiRuby::Layout::Constraint.length(10)]),
+          bottom
+        )
+      end
+      assert_equal "file:///tmp/log", RatatuiRuby.get_cell_at(0, 0).hyperlink
+      assert_equal "file:///tmp/log", RatatuiRuby.get_cell_at(0, 1).hyperlink
+    end
+  end
+
+  def test_wide_characters_keep_their_width
+    with_test_terminal(10, 1) do
+      span = RatatuiRuby::Text::Span.new(content: "你好", hyperlink: "https://example.com")
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: [RatatuiRuby::Text::Line.new(spans: [span, "!"])]))
+      assert_equal "!", RatatuiRuby.get_cell_at(4, 0).char
+      assert_nil RatatuiRuby.get_cell_at(4, 0).hyperlink
+    end
+  end
+end
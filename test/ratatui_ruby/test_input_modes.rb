@@ -0,0 +1,74 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestInputModes < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_test_terminal_starts_with_everything_off
+    with_test_terminal do
+      assert_equal false, RatatuiRuby.mouse_capture
+      refute RatatuiRuby.focus_events?
+      refute RatatuiRuby.bracketed_paste?
+    end
+  end
+
+  def test_mouse_capture_modes
+    with_test_terminal do
+      RatatuiRuby.enable_mouse_capture
+      assert_equal :all, RatatuiRuby.mouse_capture
+
+      RatatuiRuby.enable_mouse_capture(:clicks)
+      assert_equal :clicks, RatatuiRuby.mouse_capture
+
+      RatatuiRuby.disable_mouse_capture
+      assert_equal false, RatatuiRuby.mouse_capture
+    end
+  end
+
+  def test_unknown_mouse_mode
+    with_test_terminal do
+      error = assert_raises(ArgumentError) { RatatuiRuby.enable_mouse_capture(:drags) }
+      assert_equal "Unknown mouse mode: drags", error.message
+    end
+  end
+
+  def test_focus_events_toggle
+    with_test_terminal do
+      RatatuiRuby.enable_focus_events
+      assert RatatuiRuby.focus_events?
+
+      RatatuiRuby.disable_focus_events
+      refute RatatuiRuby.focus_events?
+    end
+  end
+
+  def test_bracketed_paste_toggle
+    with_test_terminal do
+      RatatuiRuby.enable_bracketed_paste
+      assert RatatuiRuby.bracketed_paste?
+
+      RatatuiRuby.disable_bracketed_paste
+      refute RatatuiRuby.bracketed_paste?
+    end
+  end
+
+  def test_toggles_survive_suspend
+    with_test_terminal do
+      RatatuiRuby.enable_mouse_capture(:clicks)
+      RatatuiRuby.suspend { nil }
+      assert_equal :clicks, RatatuiRuby.mouse_capture
+    end
+  end
+
+  def test_toggles_require_initialized_terminal
+    RatatuiRuby.restore_terminal
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.enable_mouse_capture }
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.enable_focus_events }
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.disable_bracketed_paste }
+    assert_equal false, RatatuiRuby.mouse_capture
+  end
+end
@@ -0,0 +1,69 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestInsertBefore < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def inline_terminal(&)
+    with_test_terminal(12, 6, viewport: RatatuiRuby::Viewport.inline(2), &)
+  end
+
+  def test_inserts_widget_above_viewport
+    inline_terminal do
+      RatatuiRuby.insert_before(1, RatatuiRuby::Widgets::Paragraph.new(text: "log one"))
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "status"))
+
+      assert_equal "log one     ", buffer_content[0]
+      assert_equal "status      ", buffer_content[1]
+    end
+  end
+
+  def test_inserted_lines_accumulate
+    inline_terminal do
+      RatatuiRuby.insert_before(1, RatatuiRuby::Widgets::Paragraph.new(text: "first"))
+      RatatuiRuby.insert_before(1, RatatuiRuby::Widgets::Paragraph.new(text: "second"))
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "status"))
+
+      assert_equal "first       ", buffer_content[0]
+      assert_equal "second      ", buffer_content[1]
+      assert_equal "status      ", buffer_content[2]
+    end
+  end
+
+  def test_block_form_yields_frame_sized_to_inserted_lines
+    inline_terminal do
+      area = nil
+      RatatuiRuby.insert_before(2) do |frame|
+        area = frame.area
+        frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "a\nb"), frame.area)
+      end
+
+      assert_equal 12, area.width
+      assert_equal 2, area.height
+      assert_equal "a           ", buffer_content[0]
+      assert_equal "b           ", buffer_content[1]
+    end
+  end
+
+  def test_inserted_lines_keep_styles
+    inline_terminal do
+      span = RatatuiRuby::Text::Span.new(content: "ok", style: RatatuiRuby::Style::Style.new(fg: :green))
+      RatatuiRuby.insert_before(1, RatatuiRuby::Widgets::Paragraph.new(text: RatatuiRuby::Text::Line.new(spans: [span])))
+
+      assert_equal :green, RatatuiRuby.get_cell_at(0, 0).fg
+    end
+  end
+
+  def test_requires_tree_or_block
+    inline_terminal do
+      assert_raises(ArgumentError) { RatatuiRuby.insert_before(1) }
+      assert_raises(ArgumentError) do
+        RatatuiRuby.insert_before(1, RatatuiRuby::Widgets::Paragraph.new(text: "x")) { nil }
+      end
+    end
+  end
+end
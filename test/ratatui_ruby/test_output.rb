@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestOutput < Minitest::Test
+  include PtyHelper
+
+  # Invalid targets are rejected before the terminal leaves cooked mode.
+
+  def test_unknown_symbol_raises
+    error = assert_raises(ArgumentError) { RatatuiRuby.init_terminal(output: :stderr_please) }
+    assert_equal "Unknown output: stderr_please", error.message
+  end
+
+  def test_closed_descriptor_raises
+    reader, writer = IO.pipe
+    fd = writer.fileno
+    writer.close
+    reader.close
+    assert_raises(ArgumentError) { RatatuiRuby.init_terminal(output: fd) }
+  end
+
+  def test_closed_io_raises
+    _reader, writer = IO.pipe
+    writer.close
+    assert_raises(IOError) { RatatuiRuby.init_terminal(output: writer) }
+  end
+
+  # Nothing may reach stdout, so run with it redirected to a pipe and report what
+  # arrived there.
+  def run_with_piped_stdout(viewport)
+    run_in_pty(<<~RUBY)
+      require "io/console"
+      $stdin.winsize = [5, 20]
+      reader, writer = IO.pipe
+      $stdout.reopen(writer)
+      begin
+        RatatuiRuby.init_terminal(output: :tty, viewport: #{viewport})
+        RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "hi"))
+        RatatuiRuby.restore_terminal
+        RESULT.write("drawn")
+      rescue ArgumentError => e
+        RESULT.write(e.message)
+      end
+      $stdout.flush
+      RESULT.write(" stdout=\#{reader.read_nonblock(1 << 16, exception: false).inspect}")
+    RUBY
+  end
+
+  def test_inline_viewport_requires_stdout
+    skip "Pseudo-terminals require a Unix-like platform" if Gem.win_platform?
+    output = run_with_piped_stdout("RatatuiRuby::Viewport.inline(2)")
+    assert_equal "Inline viewports can only draw on stdout stdout=:wait_readable", output
+  end
+
+  def test_fixed_viewport_leaves_stdout_alone
+    skip "Pseudo-terminals require a Unix-like platform" if Gem.win_platform?
+    area = "RatatuiRuby::Layout::Rect.new(x: 0, y: 0, width: 10, height: 2)"
+    output = run_with_piped_stdout("RatatuiRuby::Viewport.fixed(#{area})")
+    assert_equal "drawn stdout=:wait_readable", output
+  end
+end
@@ -0,0 +1,54 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestPanic < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  # The canvas renderer unwraps shape attribute reads, so a raising reader panics in Rust.
+  def with_panicking_line_color
+    line_class = RatatuiRuby::Widgets::Shape::Line
+    line_class.define_method(:color) { raise "no color" }
+    yield
+  ensure
+    line_class.remove_method(:color)
+  end
+
+  def canvas
+    line = RatatuiRuby::Widgets::Shape::Line.new(x1: 0, y1: 0, x2: 10, y2: 10, color: :red)
+    RatatuiRuby::Widgets::Canvas.new(shapes: [line], x_bounds: [0, 10], y_bounds: [0, 10])
+  end
+
+  def test_panic_while_drawing_raises_ruby_exception
+    with_test_terminal(10, 2) do
+      with_panicking_line_color do
+        error = assert_raises(RatatuiRuby::Error::Panic) { RatatuiRuby.draw(canvas) }
+        assert_match(/unwrap/, error.message)
+      end
+    end
+  end
+
+  def test_panic_inside_draw_block_raises_ruby_exception
+    with_test_terminal(10, 2) do
+      with_panicking_line_color do
+        assert_raises(RatatuiRuby::Error::Panic) do
+          RatatuiRuby.draw { |frame| frame.render_widget(canvas, frame.area) }
+        end
+      end
+    end
+  end
+
+  def test_terminal_still_draws_after_panic
+    with_test_terminal(10, 2) do
+      with_panicking_line_color do
+        assert_raises(RatatuiRuby::Error::Panic) { RatatuiRuby.draw(canvas) }
+      end
+
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "alive"))
+      assert_equal "alive     ", buffer_content[0]
+    end
+  end
+end
@@ -0,0 +1,75 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+# The test terminal never waits for input, so these run against a pseudo-terminal.
+class TestPollEventGvl < Minitest::Test
+  include PtyHelper
+
+  def setup
+    skip "Pseudo-terminals require a Unix-like platform" if Gem.win_platform?
+  end
+
+  def test_other_threads_run_while_waiting
+    ticks = run_in_pty(<<~RUBY).to_i
+      ticks = 0
+      ticker = Thread.new { loop { ticks += 1; sleep 0.01 } }
+      RatatuiRuby.poll_event(timeout: 0.5)
+      ticker.kill
+      RESULT.puts ticks
+    RUBY
+    assert_operator ticks, :>=, 10
+  end
+
+  def test_thread_raise_interrupts_the_wait
+    message, elapsed = run_in_pty(<<~RUBY).lines
+      waiter = Thread.new do
+        RatatuiRuby.poll_event(timeout: 5)
+        "returned"
+      rescue => e
+        e.message
+      end
+      sleep 0.2
+      started = Process.clock_gettime(Process::CLOCK_MONOTONIC)
+      waiter.raise("stop")
+      RESULT.puts waiter.value
+      RESULT.puts Process.clock_gettime(Process::CLOCK_MONOTONIC) - started
+    RUBY
+    assert_equal "stop\n", message
+    assert_operator elapsed.to_f, :<, 1
+  end
+
+  def test_thread_kill_interrupts_the_wait
+    result = run_in_pty(<<~RUBY)
+      waiter = Thread.new { RatatuiRuby.poll_event(timeout: 5) }
+      sleep 0.2
+      waiter.kill
+      RESULT.puts waiter.join(1) ? "stopped" : "still waiting"
+    RUBY
+    assert_equal "stopped\n", result
+  end
+
+  def test_interrupting_one_thread_leaves_another_waiting
+    raised, other = run_in_pty(<<~RUBY).lines
+      other = Thread.new do
+        started = Process.clock_gettime(Process::CLOCK_MONOTONIC)
+        RatatuiRuby.poll_event(timeout: 0.8)
+        Process.clock_gettime(Process::CLOCK_MONOTONIC) - started
+      end
+      waiter = Thread.new do
+        RatatuiRuby.poll_event(timeout: 5)
+      rescue => e
+        e.message
+      end
+      sleep 0.2
+      waiter.raise("stop")
+      RESULT.puts waiter.join(1) ? waiter.value : "still waiting"
+      RESULT.puts other.value
+    RUBY
+    assert_equal "stop\n", raised
+    assert_operator other.to_f, :>=, 0.75
+  end
+end
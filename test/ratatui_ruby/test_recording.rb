@@ -0,0 +1,114 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+require "json"
+require "pathname"
+require "tmpdir"
+
+class TestRecording < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def paragraph(text)
+    RatatuiRuby::Widgets::Paragraph.new(text:)
+  end
+
+  def cast(path)
+    header, *events = File.readlines(path).map { |line| JSON.parse(line) }
+    [header, events]
+  end
+
+  def test_records_test_terminal_frames
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "demo.cast")
+      with_test_terminal(4, 2) do
+        RatatuiRuby.record(path, title: "Demo") do
+          assert RatatuiRuby.recording?
+          RatatuiRuby.draw(paragraph("hi"))
+          RatatuiRuby.draw(paragraph("yo"))
+        end
+        refute RatatuiRuby.recording?
+      end
+
+      header, events = cast(path)
+      assert_equal({ "version" => 2, "width" => 4, "height" => 2, "title" => "Demo" }, header)
+      assert_equal [
+        [0.1, "o", "\e[H\e[2Jhi  \r\n    "],
+        [0.2, "o", "\e[H\e[2Jyo  \r\n    "],
+      ], events
+    end
+  end
+
+  def test_recordings_of_the_same_session_are_identical
+    Dir.mktmpdir do |dir|
+      paths = %w[a.cast b.cast].map { |name| File.join(dir, name) }
+      paths.each do |path|
+        with_test_terminal(4, 1) do
+          RatatuiRuby.record(path) { RatatuiRuby.draw(paragraph("x")) }
+        end
+      end
+      assert_equal File.read(paths[0]), File.read(paths[1])
+    end
+  end
+
+  def test_records_input_and_resizes
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "input.cast")
+      with_test_terminal(4, 1) do
+        RatatuiRuby.record(path, input: true) do
+          inject_keys("a", :enter)
+          inject_event(RatatuiRuby::Event::Paste.new(content: "p"))
+          3.times { RatatuiRuby.poll_event }
+          RatatuiRuby.resize_terminal(5, 2)
+        end
+      end
+
+      _header, events = cast(path)
+      assert_equal [["i", "a"], ["i", "\r"], ["i", "\e[200~p\e[201~"], ["r", "5x2"]], events.map { |e| e.drop(1) }
+    end
+  end
+
+  def test_input_is_not_recorded_by_default
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "quiet.cast")
+      with_test_terminal(4, 1) do
+        RatatuiRuby.record(path) do
+          inject_keys("a")
+          RatatuiRuby.poll_event
+        end
+      end
+      assert_empty cast(path).last
+    end
+  end
+
+  def test_restoring_the_terminal_stops_recording
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "restored.cast")
+      with_test_terminal(4, 1) do
+        RatatuiRuby.start_recording(Pathname(path))
+        RatatuiRuby.draw(paragraph("x"))
+      end
+      refute RatatuiRuby.recording?
+      assert_equal 1, cast(path).last.size
+    end
+  end
+
+  def test_requires_a_terminal
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.start_recording("unused.cast") }
+  end
+
+  def test_cannot_record_twice
+    Dir.mktmpdir do |dir|
+      with_test_terminal do
+        RatatuiRuby.record(File.join(dir, "one.cast")) do
+          assert_raises(RatatuiRuby::Error::Terminal) do
+            RatatuiRuby.start_recording(File.join(dir, "two.cast"))
+          end
+          assert RatatuiRuby.recording?
+        end
+      end
+    end
+  end
+end
@@ -0,0 +1,134 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestRenderToBuffer < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def paragraph(text, **)
+    RatatuiRuby::Widgets::Paragraph.new(text:, **)
+  end
+
+  def test_renders_a_widget
+    buffer = RatatuiRuby.render_to_buffer(paragraph("Hello"), width: 8, height: 2)
+    assert_equal 8, buffer.width
+    assert_equal 2, buffer.height
+    assert_equal ["Hello   ", "        "], buffer.lines
+    assert_equal "Hello   \n        ", buffer.to_s
+  end
+
+  def test_renders_a_block
+    buffer = RatatuiRuby.render_to_buffer(width: 4, height: 1) do |frame|
+      frame.render_widget(paragraph("hi"), frame.area)
+    end
+    assert_equal ["hi  "], buffer.lines
+  end
+
+  def test_leaves_the_test_terminal_alone
+    with_test_terminal(5, 1) do
+      RatatuiRuby.draw(paragraph("main"))
+      RatatuiRuby.render_to_buffer(paragraph("other"), width: 5, height: 1)
+      assert_equal ["main "], buffer_content
+    end
+  end
+
+  def test_does_not_need_a_terminal
+    buffer = RatatuiRuby.render_to_buffer(paragraph("x"), width: 1, height: 1)
+    assert_equal ["x"], buffer.lines
+  end
+
+  def test_cell_at
+    style = RatatuiRuby::Style::Style.new(fg: :red, modifiers: [:bold])
+    buffer = RatatuiRuby.render_to_buffer(paragraph("ab", style:), width: 2, height: 1)
+    cell = buffer.cell_at(1, 0)
+    assert_equal "b", cell.char
+    assert_equal :red, cell.fg
+    assert cell.bold?
+  end
+
+  def test_cell_at_outside_the_buffer
+    buffer = RatatuiRuby.render_to_buffer(paragraph("ab"), width: 2, height: 1)
+    assert_raises(RatatuiRuby::Error::Terminal) { buffer.cell_at(2, 0) }
+  end
+
+  def test_regions
+    text = RatatuiRuby::Text::Line.new(spans: [
+      RatatuiRuby::Text::Span.new(content: "ab", style: RatatuiRuby::Style::Style.new(fg: :red)),
+      RatatuiRuby::Text::Span.new(content: "cd"),
+    ])
+    buffer = RatatuiRuby.render_to_buffer(paragraph(text), width: 5, height: 2)
+    regions = buffer.regions
+    assert_equal [[0, 0, "ab"], [2, 0, "cd "], [0, 1, "     "]], regions.map { |r| [r.x, r.y, r.text] }
+    assert_equal :red, regions.first.fg
+    assert_equal 2, regions.first.width
+    assert_equal [], regions.first.modifiers
+    assert_nil regions.first.hyperlink
+  end
+
+  def test_each_region_without_a_block
+    buffer = RatatuiRuby.render_to_buffer(paragraph("a"), width: 1, height: 1)
+    assert_instance_of Enumerator, buffer.each_region
+    assert_equal ["a"], buffer.each_region.map(&:text)
+  end
+
+  def test_color_depth
+    style = RatatuiRuby::Style::Style.new(fg: "#ff0000")
+    buffer = RatatuiRuby.render_to_buffer(paragraph("a", style:), width: 1, height: 1, color_depth: :none)
+    assert_nil buffer.cell_at(0, 0).fg
+  end
+
+  def test_buffers_keep_links_without_holding_link_ids
+    buffers = Array.new(200) do |i|
+      span = RatatuiRuby::Text::Span.new(content: "x", hyperlink: "https://example.com/#{i}")
+      RatatuiRuby.render_to_buffer(paragraph(span), width: 1, height: 1)
+    end
+    assert_equal "https://example.com/0", buffers.first.cell_at(0, 0).hyperlink
+    assert_equal "https://example.com/199", buffers.last.cell_at(0, 0).hyperlink
+  end
+
+  def test_written_links_belong_to_the_buffer
+    span = RatatuiRuby::Text::Span.new(content: "a", hyperlink: "https://a.example")
+    buffer = RatatuiRuby.render_to_buffer(paragraph(span), width: 2, height: 1)
+    buffer.set_cell(1, 0, RatatuiRuby::Buffer::Cell.new(char: "b", hyperlink: "https://b.example"))
+    assert_equal "https://a.example", buffer.cell_at(0, 0).hyperlink
+    assert_equal "https://b.example", buffer.cell_at(1, 0).hyperlink
+  end
+
+  def test_rendering_inside_draw_keeps_the_outer_frames_cursor_and_links
+    link = ->(text, url) { paragraph(RatatuiRuby::Text::Span.new(content: text, hyperlink: url)) }
+    row = ->(y) { RatatuiRuby::Layout::Rect.new(x: 0, y:, width: 10, height: 1) }
+    panel = nil
+    with_test_terminal(10, 2) do
+      RatatuiRuby.draw do |frame|
+        frame.set_cursor_style(:underline)
+        frame.set_cursor_position(2, 1)
+        frame.render_widget(link.("outer", "https://outer.example"), row.(0))
+        panel = RatatuiRuby.render_to_buffer(width: 5, height: 1) do |inner|
+          inner.hide_cursor
+          inner.render_widget(link.("panel", "https://panel.example"), inner.area)
+        end
+        frame.render_widget(link.("later", "https://later.example"), row.(1))
+      end
+      assert_equal({ shape: :underline, blinking: false, visible: true }, cursor_style)
+      assert_equal "https://outer.example", RatatuiRuby.get_cell_at(0, 0).hyperlink
+      assert_equal "https://later.example", RatatuiRuby.get_cell_at(0, 1).hyperlink
+    end
+    assert_equal "https://panel.example", panel.cell_at(0, 0).hyperlink
+  end
+
+  def test_requires_a_widget_or_a_block
+    assert_raises(ArgumentError) { RatatuiRuby.render_to_buffer(width: 1, height: 1) }
+    assert_raises(ArgumentError) do
+      RatatuiRuby.render_to_buffer(paragraph("a"), width: 1, height: 1) { nil }
+    end
+  end
+
+  def test_hides_native_helpers
+    buffer = RatatuiRuby.render_to_buffer(paragraph("a"), width: 1, height: 1)
+    refute_respond_to buffer, :_content
+    refute_respond_to RatatuiRuby, :_render_to_buffer
+  end
+end
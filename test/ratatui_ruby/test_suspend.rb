@@ -0,0 +1,42 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestSuspend < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_returns_block_value
+    with_test_terminal do
+      assert_equal :edited, RatatuiRuby.suspend { :edited }
+    end
+  end
+
+  def test_next_draw_repaints_everything
+    with_test_terminal(10, 2) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "hello"))
+      RatatuiRuby.suspend { nil }
+
+      assert_equal "          ", buffer_content[0]
+
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "hello"))
+      assert_equal "hello     ", buffer_content[0]
+    end
+  end
+
+  def test_resumes_when_block_raises
+    with_test_terminal(10, 2) do
+      assert_raises(RuntimeError) { RatatuiRuby.suspend { raise "editor crashed" } }
+
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "back"))
+      assert_equal "back      ", buffer_content[0]
+    end
+  end
+
+  def test_requires_initialized_terminal
+    RatatuiRuby.restore_terminal
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.suspend { nil } }
+  end
+end
@@ -0,0 +1,54 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+# The test terminal writes no escape sequences, so these draw to a pipe from a
+# process whose controlling terminal is a pseudo-terminal.
+class TestSynchronizedOutput < Minitest::Test
+  include PtyHelper
+
+  BEGIN_UPDATE = "\e[?2026h"
+  END_UPDATE = "\e[?2026l"
+
+  def setup
+    skip "Pseudo-terminals require a Unix-like platform" if Gem.win_platform?
+  end
+
+  def draw_once(synchronized_output:)
+    run_in_pty(<<~RUBY)
+      require "io/console"
+      $stdin.winsize = [3, 10]
+      reader, writer = IO.pipe
+      RatatuiRuby.init_terminal(output: writer, synchronized_output: #{synchronized_output})
+      RatatuiRuby.draw do |frame|
+        writer.syswrite("<render>")
+        frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "hi"), frame.area)
+      end
+      RatatuiRuby.restore_terminal
+      RESULT.write(reader.read_nonblock(1 << 16))
+    RUBY
+  end
+
+  def test_update_brackets_the_written_frame
+    output = draw_once(synchronized_output: true)
+    assert_equal 1, output.scan(BEGIN_UPDATE).size
+    rendered = output.index("<render>")
+    began = output.index(BEGIN_UPDATE)
+    drawn = output.index("hi")
+    ended = output.index(END_UPDATE, began)
+    refute_nil ended
+    assert_operator rendered, :<, began, "The update should start after Ruby renders the frame"
+    assert_operator began, :<, drawn
+    assert_operator drawn, :<, ended
+  end
+
+  def test_disabled
+    output = draw_once(synchronized_output: false)
+    assert_includes output, "hi"
+    refute_includes output, BEGIN_UPDATE
+    refute_includes output, END_UPDATE
+  end
+end
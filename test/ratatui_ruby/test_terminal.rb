@@ -0,0 +1,136 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestTerminal < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def paragraph(text)
+    RatatuiRuby::Widgets::Paragraph.new(text:)
+  end
+
+  def link(text, url)
+    paragraph(RatatuiRuby::Text::Span.new(content: text, hyperlink: url))
+  end
+
+  def row(y)
+    RatatuiRuby::Layout::Rect.new(x: 0, y:, width: 10, height: 1)
+  end
+
+  def test_headless_terminals_are_independent
+    left = RatatuiRuby::Terminal.new(5, 1)
+    right = RatatuiRuby::Terminal.new(5, 1)
+    left.draw(paragraph("left"))
+    right.draw { |frame| frame.render_widget(paragraph("right"), frame.area) }
+    assert_equal ["left "], left.buffer_content
+    assert_equal ["right"], right.buffer_content
+    refute left.default?
+  end
+
+  def test_headless_terminal_leaves_module_terminal_alone
+    with_test_terminal(5, 1) do
+      RatatuiRuby.draw(paragraph("main"))
+      RatatuiRuby::Terminal.new(5, 1).draw(paragraph("other"))
+      assert_equal ["main "], buffer_content
+      assert_equal 1, RatatuiRuby.last_draw_stats.frame_count
+    end
+  end
+
+  def test_event_queues_are_separate
+    terminal = RatatuiRuby::Terminal.new(5, 1)
+    terminal.inject_event(RatatuiRuby::Event::Key.new(code: "q"))
+    with_test_terminal do
+      assert RatatuiRuby.poll_event.none?
+    end
+    assert_equal "q", terminal.poll_event.code
+    assert terminal.poll_event(timeout: nil).none?
+  end
+
+  def test_clear_events
+    terminal = RatatuiRuby::Terminal.new(5, 1)
+    terminal.inject_event(RatatuiRuby::Event::Paste.new(content: "hi"))
+    terminal.clear_events
+    assert terminal.poll_event.none?
+  end
+
+  def test_draw_stats
+    terminal = RatatuiRuby::Terminal.new(10, 1)
+    assert_nil terminal.last_draw_stats
+    terminal.draw(paragraph("Hello"))
+    stats = terminal.draw(paragraph("Jello"))
+    assert_equal 2, stats.frame_count
+    assert_equal 1, stats.cells_changed
+    assert_equal stats, terminal.last_draw_stats
+  end
+
+  def test_nested_draws_count_only_their_own_cells
+    terminal = RatatuiRuby::Terminal.new(10, 1)
+    with_test_terminal(10, 1) do
+      stats = RatatuiRuby.draw do |frame|
+        inner = terminal.draw(paragraph("Hello"))
+        assert_equal 5, inner.cells_changed
+        frame.render_widget(paragraph("Hi"), frame.area)
+      end
+      assert_equal 2, stats.cells_changed
+    end
+  end
+
+  def test_nested_draw_keeps_the_outer_frames_cursor_and_links
+    terminal = RatatuiRuby::Terminal.new(5, 1)
+    with_test_terminal(10, 2) do
+      RatatuiRuby.draw do |frame|
+        frame.set_cursor_style(:bar)
+        frame.set_cursor_position(1, 1)
+        frame.render_widget(link("outer", "https://outer.example"), row(0))
+        terminal.draw do |inner|
+          inner.hide_cursor
+          inner.render_widget(link("inner", "https://inner.example"), inner.area)
+        end
+        frame.render_widget(link("later", "https://later.example"), row(1))
+      end
+      assert_equal({ shape: :bar, blinking: false, visible: true }, cursor_style)
+      assert_equal "https://outer.example", RatatuiRuby.get_cell_at(0, 0).hyperlink
+      assert_equal "https://later.example", RatatuiRuby.get_cell_at(0, 1).hyperlink
+    end
+    assert_equal "https://inner.example", terminal.cell_at(0, 0).hyperlink
+  end
+
+  def test_cell_at_and_color_depth
+    terminal = RatatuiRuby::Terminal.new(3, 1, color_depth: :none)
+    style = RatatuiRuby::Style::Style.new(fg: :red)
+    terminal.draw(RatatuiRuby::Widgets::Paragraph.new(text: "abc", style:))
+    cell = terminal.cell_at(1, 0)
+    assert_equal "b", cell.char
+    assert_nil cell.fg
+  end
+
+  def test_cursor_position_and_resize
+    terminal = RatatuiRuby::Terminal.new(10, 2)
+    terminal.draw { |frame| frame.set_cursor_position(3, 1) }
+    assert_equal({ x: 3, y: 1 }, terminal.cursor_position)
+    terminal.resize(4, 1)
+    terminal.draw { |frame| assert_equal 4, frame.area.width }
+  end
+
+  def test_inspecting_while_drawing_raises
+    terminal = RatatuiRuby::Terminal.new(5, 1)
+    assert_raises(RatatuiRuby::Error::Terminal) do
+      terminal.draw { terminal.buffer_content }
+    end
+  end
+
+  def test_default_forwards_to_module_functions
+    with_test_terminal(5, 1) do
+      terminal = RatatuiRuby::Terminal.default
+      assert terminal.default?
+      assert_same terminal, RatatuiRuby::Terminal.default
+      terminal.draw(paragraph("hey"))
+      assert_equal ["hey  "], buffer_content
+      inject_keys("x")
+      assert_equal "x", terminal.poll_event.code
+    end
+  end
+end
@@ -0,0 +1,50 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestTerminalColors < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_test_terminal_reports_unknown_colors
+    with_test_terminal do
+      colors = RatatuiRuby.query_terminal_colors(palette: [0, 15])
+      assert_nil colors.foreground
+      assert_nil colors.background
+      assert_equal({ 0 => nil, 15 => nil }, colors.palette)
+      assert_nil colors.dark?
+      assert_nil colors.light?
+    end
+  end
+
+  def test_query_does_not_produce_events
+    with_test_terminal do
+      RatatuiRuby.query_terminal_colors
+      assert RatatuiRuby.poll_event.none?
+    end
+  end
+
+  def test_tui_delegates
+    with_test_terminal do
+      tui = RatatuiRuby::TUI.new
+      assert_instance_of RatatuiRuby::TerminalColors, tui.query_terminal_colors
+    end
+  end
+
+  def test_negative_timeout_raises
+    with_test_terminal do
+      assert_raises(ArgumentError) { RatatuiRuby.query_terminal_colors(timeout: -1) }
+    end
+  end
+
+  def test_dark_and_light
+    dark = RatatuiRuby::TerminalColors.new(foreground: "#cdd6f4", background: "#1e1e2e", palette: {})
+    light = RatatuiRuby::TerminalColors.new(foreground: "#4c4f69", background: "#eff1f5", palette: {})
+    assert dark.dark?
+    refute dark.light?
+    assert light.light?
+    refute light.dark?
+  end
+end
@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestTitleAndClipboard < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_set_title
+    with_test_terminal do
+      assert_nil terminal_title
+
+      RatatuiRuby.set_title("notes.md")
+      assert_equal "notes.md", terminal_title
+    end
+  end
+
+  def test_restore_title
+    with_test_terminal do
+      RatatuiRuby.set_title("notes.md")
+      RatatuiRuby.restore_title
+      assert_nil terminal_title
+    end
+  end
+
+  def test_restore_terminal_restores_title
+    with_test_terminal do
+      RatatuiRuby.set_title("notes.md")
+    end
+    assert_nil RatatuiRuby.get_title
+  end
+
+  def test_copy_to_clipboard
+    with_test_terminal do
+      RatatuiRuby.copy_to_clipboard("first")
+      RatatuiRuby.copy_to_clipboard("ünïcödé ✓", selection: :primary)
+      assert_equal ["first", "ünïcödé ✓"], clipboard_writes
+    end
+  end
+
+  def test_unknown_selection
+    with_test_terminal do
+      error = assert_raises(ArgumentError) { RatatuiRuby.copy_to_clipboard("x", selection: :secondary) }
+      assert_equal "Unknown clipboard selection: secondary", error.message
+    end
+  end
+
+  def test_tui_delegates
+    with_test_terminal do
+      tui = RatatuiRuby::TUI.new
+      tui.set_title("from tui")
+      tui.copy_to_clipboard("copied")
+      assert_equal "from tui", terminal_title
+      assert_equal ["copied"], clipboard_writes
+    end
+  end
+
+  def test_requires_initialized_terminal
+    RatatuiRuby.restore_terminal
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.set_title("x") }
+    assert_raises(RatatuiRuby::Error::Terminal) { RatatuiRuby.copy_to_clipboard("x") }
+  end
+end
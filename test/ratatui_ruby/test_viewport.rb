@@ -0,0 +1,70 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestViewport < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  def test_fullscreen_factory
+    viewport = RatatuiRuby::Viewport.fullscreen
+    assert_equal :fullscreen, viewport.type
+    assert_predicate viewport, :fullscreen?
+  end
+
+  def test_inline_factory
+    viewport = RatatuiRuby::Viewport.inline(4)
+    assert_equal :inline, viewport.type
+    assert_equal 4, viewport.height
+    assert_predicate viewport, :inline?
+  end
+
+  def test_fixed_factory
+    area = RatatuiRuby::Layout::Rect.new(x: 1, y: 2, width: 10, height: 3)
+    viewport = RatatuiRuby::Viewport.fixed(area)
+    assert_equal :fixed, viewport.type
+    assert_equal area, viewport.area
+    assert_predicate viewport, :fixed?
+  end
+
+  def test_resolve
+    assert_equal RatatuiRuby::Viewport.fullscreen, RatatuiRuby::Viewport.resolve(nil)
+    assert_equal RatatuiRuby::Viewport.fullscreen, RatatuiRuby::Viewport.resolve(:fullscreen)
+    inline = RatatuiRuby::Viewport.inline(2)
+    assert_same inline, RatatuiRuby::Viewport.resolve(inline)
+    assert_raises(ArgumentError) { RatatuiRuby::Viewport.resolve(:sideways) }
+  end
+
+  def test_inline_viewport_limits_frame_area
+    with_test_terminal(20, 10, viewport: RatatuiRuby::Viewport.inline(3)) do
+      RatatuiRuby.draw do |frame|
+        assert_equal 20, frame.area.width
+        assert_equal 3, frame.area.height
+      end
+    end
+  end
+
+  def test_inline_viewport_draws_in_its_rows_only
+    with_test_terminal(10, 5, viewport: RatatuiRuby::Viewport.inline(2)) do
+      RatatuiRuby.draw(RatatuiRuby::Widgets::Paragraph.new(text: "one\ntwo\nthree"))
+
+      assert_equal "one       ", buffer_content[0]
+      assert_equal "two       ", buffer_content[1]
+      assert_equal "          ", buffer_content[2]
+    end
+  end
+
+  def test_fixed_viewport_uses_given_area
+    area = RatatuiRuby::Layout::Rect.new(x: 2, y: 1, width: 5, height: 2)
+    with_test_terminal(10, 5, viewport: RatatuiRuby::Viewport.fixed(area)) do
+      RatatuiRuby.draw do |frame|
+        assert_equal area, frame.area
+        frame.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: "hi"), frame.area)
+      end
+
+      assert_equal "  hi      ", buffer_content[1]
+    end
+  end
+end
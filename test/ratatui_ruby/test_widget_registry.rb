@@ -0,0 +1,180 @@
+# frozen_string_literal: true
+
+# SPDX-FileCopyrightText: 2025 Kerrick Long <me@kerricklong.com>
+# SPDX-License-Identifier: AGPL-3.0-or-later
+
+require "test_helper"
+
+class TestWidgetRegistry < Minitest::Test
+  include RatatuiRuby::TestHelper
+
+  # Renderers stay registered for the whole process, so every test uses classes of its own.
+
+  class FancyParagraph < RatatuiRuby::Widgets::Paragraph; end
+
+  class LoudParagraph < RatatuiRuby::Widgets::Paragraph
+    def render(area)
+      [RatatuiRuby::Draw.string(area.x, area.y, "LOUD")]
+    end
+  end
+
+  class Greeting
+    def render(area, buffer)
+      buffer.set_string(area.x, area.y, "hello")
+    end
+  end
+
+  class FrenchGreeting < Greeting; end
+
+  Money = Struct.new(:cents)
+  class Refund < Money; end
+  class Chargeback < Money; end
+
+  module Badge
+    def label = "badge"
+  end
+
+  class BadgedThing
+    include Badge
+  end
+
+  class Unrenderable; end
+
+  class FancyList < RatatuiRuby::Widgets::List; end
+  class TallyList < RatatuiRuby::Widgets::List; end
+
+  class Invoice
+    def render(area)
+      [RatatuiRuby::Draw.string(area.x, area.y, "invoice")]
+    end
+  end
+
+  Counter = Struct.new(:name)
+  Count = Struct.new(:value)
+
+  def render(widget, width: 8, height: 1)
+    RatatuiRuby.render_to_buffer(widget, width:, height:).lines
+  end
+
+  def test_subclass_of_a_built_in_widget
+    assert_equal ["fancy   "], render(FancyParagraph.new(text: "fancy"))
+  end
+
+  def test_subclass_render_method_wins_over_the_built_in
+    assert_equal ["LOUD    "], render(LoudParagraph.new(text: "loud"))
+  end
+
+  def test_subclass_of_a_custom_widget
+    assert_equal ["hello   "], render(FrenchGreeting.new)
+  end
+
+  def test_line_chart
+    chart = RatatuiRuby::Widgets::LineChart.new(
+      datasets: [],
+      block: RatatuiRuby::Widgets::Block.new(title: "LC")
+    )
+    assert_equal "┌LC──────────┐", render(chart, width: 14, height: 4).first
+  end
+
+  def test_register_widget_with_a_block
+    RatatuiRuby.register_widget(Refund) do |refund, area, buffer|
+      buffer.set_string(area.x, area.y, format("-$%.2f", refund.cents / 100.0))
+    end
+    assert_equal ["-$3.00  "], render(Refund.new(300))
+  end
+
+  def test_register_widget_with_a_callable
+    renderer = Object.new
+    def renderer.call(thing, area, buffer)
+      buffer.render_widget(RatatuiRuby::Widgets::Paragraph.new(text: thing.label), area)
+    end
+    RatatuiRuby.register_widget(Badge, renderer)
+    assert_equal ["badge   "], render(BadgedThing.new)
+  end
+
+  def test_registered_renderer_wins_over_an_inherited_one
+    RatatuiRuby.register_widget(Money) { |_money, area, buffer| buffer.set_string(area.x, area.y, "money") }
+    RatatuiRuby.register_widget(Chargeback) { |_money, area, buffer| buffer.set_string(area.x, area.y, "reversed") }
+    assert_equal ["reversed"], render(Chargeback.new(300))
+  end
+
+  def test_registering_after_drawing_replaces_the_render_method
+    assert_equal ["invoice "], render(Invoice.new)
+    RatatuiRuby.register_widget(Invoice) { |_invoice, area, buffer| buffer.set_string(area.x, area.y, "billed") }
+    assert_equal ["billed  "], render(Invoice.new)
+  end
+
+  def test_an_object_can_render_differently_from_its_class
+    assert_equal ["hello   "], render(Greeting.new)
+    shouty = Greeting.new
+    def shouty.render(area, buffer)
+      buffer.set_string(area.x, area.y, "HELLO")
+    end
+    assert_equal ["HELLO   "], render(shouty)
+    assert_equal ["hello   "], render(Greeting.new)
+  end
+
+  def test_unknown_widget_raises
+    error = assert_raises(TypeError) { render(Unrenderable.new) }
+    assert_match(/Don't know how to render TestWidgetRegistry::Unrenderable/, error.message)
+    assert_match(/register_widget/, error.message)
+  end
+
+  def test_unknown_widget_inside_a_container_raises
+    layout = RatatuiRuby::Layout::Layout.new(
+      direction: :vertical,
+      constraints: [RatatuiRuby::Layout::Constraint.length(1)],
+      children: [Unrenderable.new]
+    )
+    containers = [
+      layout,
+      RatatuiRuby::Widgets::Block.new(children: [Unrenderable.new]),
+      RatatuiRuby::Widgets::Overlay.new(layers: [Unrenderable.new]),
+    ]
+    containers.each do |container|
+      error = assert_raises(TypeError) { render(container, height: 3) }
+      assert_match(/Don't know how to render TestWidgetRegistry::Unrenderable/, error.message)
+    end
+  end
+
+  def test_subclasses_render_statefully
+    list = FancyList.new(items: %w[a b c])
+    state = RatatuiRuby::ListState.new(1)
+    with_test_terminal(4, 3) do
+      RatatuiRuby.draw { |frame| frame.render_stateful_widget(list, frame.area, state) }
+      assert_equal ["  a ", "> b ", "  c "], buffer_content
+    end
+  end
+
+  def test_register_stateful_widget
+    RatatuiRuby.register_stateful_widget(Counter, Count) do |counter, area, buffer, count|
+      buffer.set_string(area.x, area.y, "#{counter.name}: #{count.value}")
+    end
+    with_test_terminal(10, 1) do
+      RatatuiRuby.draw { |frame| frame.render_stateful_widget(Counter.new("hits"), frame.area, Count.new(3)) }
+      assert_equal ["hits: 3   "], buffer_content
+    end
+  end
+
+  def test_registering_after_drawing_statefully_replaces_the_built_in
+    list = TallyList.new(items: %w[a b c])
+    state = RatatuiRuby::ListState.new(1)
+    with_test_terminal(4, 3) do
+      RatatuiRuby.draw { |frame| frame.render_stateful_widget(list, frame.area, state) }
+      assert_equal ["  a ", "> b ", "  c "], buffer_content
+
+      RatatuiRuby.register_stateful_widget(TallyList, RatatuiRuby::ListState) do |tally, area, buffer, tally_state|
+        buffer.set_string(area.x, area.y, "#{tally_state.selected}/#{tally.items.size}")
+      end
+      RatatuiRuby.draw { |frame| frame.render_stateful_widget(list, frame.area, state) }
+      assert_equal ["1/3 ", "    ", "    "], buffer_content
+    end
+  end
+
+  def test_register_widget_validates_its_arguments
+    assert_raises(ArgumentError) { RatatuiRuby.register_widget(:money) { nil } }
+    assert_raises(ArgumentError) { RatatuiRuby.register_widget(Unrenderable) }
+    assert_raises(ArgumentError) { RatatuiRuby.register_widget(Unrenderable, proc {}) { nil } }
+    assert_raises(ArgumentError) { RatatuiRuby.register_stateful_widget(Unrenderable, nil) { nil } }
+  end
+end
@@ -50,3 +50,37 @@ RatatuiRuby.experimental_warnings = false
 require "minitest/autorun"
 
 require "ratatui_ruby/test_helper"
+
+require "pty"
+require "rbconfig"
+require "tempfile"
+
+# Runs Ruby code in a separate process whose controlling terminal is a pseudo-terminal.
+#
+# Use it to exercise code paths that need a real terminal, which the test terminal
+# replaces. The code runs with the library loaded and writes its findings to +RESULT+;
+# whatever it writes there is returned. The child is killed after +timeout+ seconds.
+module PtyHelper
+  def run_in_pty(code, timeout: 10)
+    Tempfile.create("ratatui_ruby_pty") do |result|
+      script = "require 'ratatui_ruby'\nRESULT = File.open(ARGV[0], 'w')\nRESULT.sync = true\n#{code}"
+      lib = File.expand_path("../lib", __dir__)
+      PTY.spawn(RbConfig.ruby, "-I", lib, "-e", script, result.path) do |reader, _writer, pid|
+        deadline = Process.clock_gettime(Process::CLOCK_MONOTONIC) + timeout
+        loop do
+          remaining = deadline - Process.clock_gettime(Process::CLOCK_MONOTONIC)
+          if remaining <= 0
+            Process.kill(:KILL, pid)
+            break
+          end
+          # Drain the terminal so the child never blocks writing to it.
+          reader.read_nonblock(4096) if reader.wait_readable(remaining)
+        rescue Errno::EIO, EOFError
+          break
+        end
+        Process.wait(pid)
+      end
+      File.read(result.path)
+    end
+  end
+end